@@ -1,53 +1,56 @@
 use std::collections::BinaryHeap;
 
-fn food_per_elf(input: &str) -> Vec<u32> {
-    input
-        .split("\n\n")
-        .map(|lines| {
-            lines
-                .lines()
-                .map(|line| line.parse::<u32>().unwrap_or(0))
-                .sum::<u32>()
-        })
-        .collect()
-}
+use advent_of_code::{Params, Solution};
+
+struct Day01;
+
+impl Solution for Day01 {
+    /// Total calories carried by each elf.
+    type Parsed<'a> = Vec<u32>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> advent_of_code::Result<Self::Parsed<'_>> {
+        input
+            .split("\n\n")
+            .map(|lines| lines.lines().map(|line| line.parse::<u32>()).sum())
+            .collect::<Result<_, _>>()
+            .map_err(Into::into)
+    }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    Some(
-        food_per_elf(input)
-            .iter()
-            .reduce(|a, b| a.max(b))
-            .copied()
-            .unwrap(),
-    )
-}
+    fn part_one(food: &Self::Parsed<'_>, _: &Params) -> Option<u32> {
+        food.iter().max().copied()
+    }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let food: Vec<u32> = food_per_elf(input);
-    let maxes = BinaryHeap::from_iter(food);
+    fn part_two(food: &Self::Parsed<'_>, _: &Params) -> Option<u32> {
+        let maxes = BinaryHeap::from_iter(food.iter().copied());
 
-    Some(maxes.iter().take(3).sum::<u32>())
+        Some(maxes.into_sorted_vec().iter().rev().take(3).sum::<u32>())
+    }
 }
 
 fn main() {
     let input = &advent_of_code::read_file("inputs", 1);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(Day01, input);
 }
 
+advent_of_code::aoc_test!(
+    Day01,
+    day = 1,
+    part_one = Some(24000),
+    part_two = Some(45000)
+);
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::generate::{self, Rng};
 
     #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 1);
-        assert_eq!(part_one(&input), Some(24000));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 1);
-        assert_eq!(part_two(&input), Some(45000));
+    fn solves_generated_input() {
+        let food = Day01::parse(&generate::day01(&mut Rng::new(0), 250)).unwrap();
+        assert_eq!(food.len(), 250);
+        let params = Params::default();
+        assert!(Day01::part_one(&food, &params) < Day01::part_two(&food, &params));
     }
 }
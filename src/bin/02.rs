@@ -1,3 +1,7 @@
+use std::str::FromStr;
+
+use advent_of_code::{Params, ParseError, Solution};
+
 #[derive(Debug, Clone, Copy)]
 enum RPS {
     Rock,
@@ -54,20 +58,24 @@ impl RPSMatch {
     }
 }
 
-impl From<&str> for RPSMatch {
-    fn from(line: &str) -> Self {
-        let mut chars = line.split(' ');
-        let p1 = match chars.next().unwrap() {
+impl FromStr for RPSMatch {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let (p1, outcome) = line
+            .split_once(' ')
+            .ok_or_else(|| ParseError::new(format!("Missing space: {line}")))?;
+        let p1 = match p1 {
             "A" => RPS::Rock,
             "B" => RPS::Paper,
             "C" => RPS::Scissors,
-            x => panic!("Unknown string: {x}"),
+            x => return Err(ParseError::new(format!("Unknown string: {x}"))),
         };
-        let outcome = match chars.next().unwrap() {
+        let outcome = match outcome {
             "X" => Outcome::Lose,
             "Y" => Outcome::Draw,
             "Z" => Outcome::Win,
-            x => panic!("Unknown string: {x}"),
+            x => return Err(ParseError::new(format!("Unknown string: {x}"))),
         };
         let p2 = {
             if RPS::Rock.fight(p1) == outcome {
@@ -78,49 +86,46 @@ impl From<&str> for RPSMatch {
                 RPS::Scissors
             }
         };
-        Self { p1, p2 }
+        Ok(Self { p1, p2 })
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let m = RPSMatch::from("A Z");
-    println!("{m:?}");
-    let score = input
-        .lines()
-        .map(|line| line.into())
-        .map(|m: RPSMatch| m.score().1)
-        .sum();
-    Some(score)
-}
+struct Day02;
+
+impl Solution for Day02 {
+    type Parsed<'a> = Vec<RPSMatch>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> advent_of_code::Result<Self::Parsed<'_>> {
+        input.lines().map(str::parse).collect()
+    }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let score = input
-        .lines()
-        .map(|line| line.into())
-        .map(|m: RPSMatch| m.score().1)
-        .sum::<u32>();
-    Some(score)
+    fn part_one(matches: &Self::Parsed<'_>, _: &Params) -> Option<u32> {
+        Some(matches.iter().map(|m| m.score().1).sum())
+    }
+
+    fn part_two(matches: &Self::Parsed<'_>, _: &Params) -> Option<u32> {
+        Some(matches.iter().map(|m| m.score().1).sum())
+    }
 }
 
 fn main() {
     let input = &advent_of_code::read_file("inputs", 2);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(Day02, input);
 }
 
+advent_of_code::aoc_test!(Day02, day = 2, part_one = Some(0), part_two = Some(0));
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::generate::{self, Rng};
 
     #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 2);
-        assert_eq!(part_one(&input), Some(0));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 2);
-        assert_eq!(part_two(&input), Some(0));
+    fn solves_generated_input() {
+        let matches = Day02::parse(&generate::day02(&mut Rng::new(0), 2500)).unwrap();
+        assert_eq!(matches.len(), 2500);
+        assert!(Day02::part_two(&matches, &Params::default()) >= Some(2500));
     }
 }
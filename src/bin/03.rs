@@ -2,16 +2,11 @@
 #![feature(iter_next_chunk)]
 use std::collections::HashSet;
 
+use advent_of_code::{Params, ParseError, Solution};
+
 fn halves(s: &str) -> [&str; 2] {
-    let len = s.len();
-    if len % 2 != 0 {
-        panic!("Not divisibleby two");
-    } else {
-        let half_len = len / 2;
-        let s1 = &s[0..half_len];
-        let s2 = &s[half_len..len];
-        [s1, s2]
-    }
+    let (s1, s2) = s.split_at(s.len() / 2);
+    [s1, s2]
 }
 
 fn priority(letter: &char) -> u32 {
@@ -22,28 +17,6 @@ fn priority(letter: &char) -> u32 {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    input
-        .lines()
-        .map(halves)
-        .map(|halves| {
-            let set1 = halves[0].chars().fold(HashSet::new(), |mut set, letter| {
-                set.insert(letter);
-                set
-            });
-            let set2 = halves[1].chars().fold(HashSet::new(), |mut set, letter| {
-                set.insert(letter);
-                set
-            });
-            let mut sum = 0;
-            for letter in set1.intersection(&set2) {
-                sum += priority(letter);
-            }
-            sum
-        })
-        .reduce(|a, b| a + b)
-}
-
 fn intersection(sets: [HashSet<char>; 3]) -> char {
     for letters0 in sets[0].iter() {
         for letters1 in sets[1].iter() {
@@ -57,44 +30,88 @@ fn intersection(sets: [HashSet<char>; 3]) -> char {
     panic!("no intersection found");
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let pack_groups = input.lines().array_chunks::<3>();
+struct Day03;
 
-    let badges = pack_groups.map(|pack_group| {
-        let mut items = pack_group.iter().map(|pack| {
-            let mut set = HashSet::new();
-            for letter in pack.chars() {
-                set.insert(letter);
-            }
-            set
+impl Solution for Day03 {
+    type Parsed<'a> = Vec<String>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> advent_of_code::Result<Self::Parsed<'_>> {
+        input
+            .lines()
+            .map(|line| {
+                if line.len() % 2 != 0 {
+                    Err(ParseError::new(format!("Not divisible by two: {line}")))
+                } else if let Some(c) = line.chars().find(|c| !c.is_ascii_alphabetic()) {
+                    Err(ParseError::new(format!("Unknown letter {c}")))
+                } else {
+                    Ok(line.to_string())
+                }
+            })
+            .collect()
+    }
+
+    fn part_one(rucksacks: &Self::Parsed<'_>, _: &Params) -> Option<u32> {
+        rucksacks
+            .iter()
+            .map(|r| halves(r))
+            .map(|halves| {
+                let set1 = halves[0].chars().fold(HashSet::new(), |mut set, letter| {
+                    set.insert(letter);
+                    set
+                });
+                let set2 = halves[1].chars().fold(HashSet::new(), |mut set, letter| {
+                    set.insert(letter);
+                    set
+                });
+                let mut sum = 0;
+                for letter in set1.intersection(&set2) {
+                    sum += priority(letter);
+                }
+                sum
+            })
+            .reduce(|a, b| a + b)
+    }
+
+    fn part_two(rucksacks: &Self::Parsed<'_>, _: &Params) -> Option<u32> {
+        let pack_groups = rucksacks.iter().array_chunks::<3>();
+
+        let badges = pack_groups.map(|pack_group| {
+            let mut items = pack_group.iter().map(|pack| {
+                let mut set = HashSet::new();
+                for letter in pack.chars() {
+                    set.insert(letter);
+                }
+                set
+            });
+            items.next_chunk::<3>().unwrap()
         });
-        items.next_chunk::<3>().unwrap()
-    });
-    badges
-        .map(intersection)
-        .map(|c| priority(&c))
-        .reduce(|a, b| a + b)
+        badges
+            .map(intersection)
+            .map(|c| priority(&c))
+            .reduce(|a, b| a + b)
+    }
 }
 
 fn main() {
     let input = &advent_of_code::read_file("inputs", 3);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(Day03, input);
 }
 
+advent_of_code::aoc_test!(Day03, day = 3, part_one = None, part_two = None);
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::generate::{self, Rng};
 
     #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 3);
-        assert_eq!(part_one(&input), None);
-    }
-
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 3);
-        assert_eq!(part_two(&input), None);
+    fn solves_generated_input() {
+        let rucksacks = Day03::parse(&generate::day03(&mut Rng::new(0), 300)).unwrap();
+        assert_eq!(rucksacks.len(), 300);
+        let params = Params::default();
+        assert!(Day03::part_one(&rucksacks, &params) >= Some(300));
+        assert!(Day03::part_two(&rucksacks, &params) >= Some(100));
     }
 }
@@ -1,93 +1,81 @@
 use std::str::FromStr;
 
-#[derive(Debug, Clone, Copy)]
-struct Range {
-    start: u32,
-    end: u32,
-}
-
-impl FromStr for Range {
-    type Err = ();
+use advent_of_code::helpers::interval::Interval;
+use advent_of_code::{Params, ParseError, Solution};
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut matches = s.split('-');
-        let start = matches.next().map(|a| a.parse::<u32>());
-        let end = matches.next().map(|a| a.parse::<u32>());
-        match (start, end) {
-            (Some(Ok(a)), Some(Ok(b))) => Ok(Range { start: a, end: b }),
-            _ => Err(()),
-        }
-    }
+fn parse_interval(s: &str) -> Option<Interval> {
+    let (start, end) = s.split_once('-')?;
+    Interval::new(start.parse().ok()?, end.parse().ok()?)
 }
 
-struct RangePair(Range, Range);
+struct RangePair(Interval, Interval);
 
 impl RangePair {
     fn completely_overlap(&self) -> bool {
-        self.0.start <= self.1.start && self.0.end >= self.1.end
-            || self.0.start >= self.1.start && self.0.end <= self.1.end
+        self.0.contains_interval(&self.1) || self.1.contains_interval(&self.0)
     }
 
     fn partially_overlap(&self) -> bool {
-        self.0.start <= self.1.start && self.1.start <= self.0.end
-            || self.0.start <= self.1.end && self.1.end <= self.0.end
-            || self.completely_overlap()
+        self.0.overlaps(&self.1)
     }
 }
 
 impl FromStr for RangePair {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut halves = s.split(',');
-        let r1 = halves.next().map(|a| a.parse::<Range>());
-        let r2 = halves.next().map(|a| a.parse::<Range>());
-        match (r1, r2) {
-            (Some(Ok(a)), Some(Ok(b))) => Ok(RangePair(a, b)),
-            _ => Err(()),
+        let (r1, r2) = s
+            .split_once(',')
+            .ok_or_else(|| ParseError::new(format!("Not a pair: {s}")))?;
+        match (parse_interval(r1), parse_interval(r2)) {
+            (Some(a), Some(b)) => Ok(RangePair(a, b)),
+            _ => Err(ParseError::new(format!("Invalid ranges: {s}"))),
         }
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    Some(
-        input
-            .lines()
-            .map(|l| l.parse::<RangePair>().unwrap())
-            .filter(|pair| pair.completely_overlap())
-            .count() as u32,
-    )
-}
+struct Day04;
+
+impl Solution for Day04 {
+    type Parsed<'a> = Vec<RangePair>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> advent_of_code::Result<Self::Parsed<'_>> {
+        input.lines().map(str::parse).collect()
+    }
+
+    fn part_one(pairs: &Self::Parsed<'_>, _: &Params) -> Option<u32> {
+        Some(
+            pairs
+                .iter()
+                .filter(|pair| pair.completely_overlap())
+                .count() as u32,
+        )
+    }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    Some(
-        input
-            .lines()
-            .map(|l| l.parse::<RangePair>().unwrap())
-            .filter(|pair| pair.partially_overlap())
-            .count() as u32,
-    )
+    fn part_two(pairs: &Self::Parsed<'_>, _: &Params) -> Option<u32> {
+        Some(pairs.iter().filter(|pair| pair.partially_overlap()).count() as u32)
+    }
 }
 
 fn main() {
     let input = &advent_of_code::read_file("inputs", 4);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(Day04, input);
 }
 
+advent_of_code::aoc_test!(Day04, day = 4, part_one = Some(2), part_two = Some(4));
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::generate::{self, Rng};
 
     #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 4);
-        assert_eq!(part_one(&input), Some(2));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 4);
-        assert_eq!(part_two(&input), Some(4));
+    fn solves_generated_input() {
+        let pairs = Day04::parse(&generate::day04(&mut Rng::new(0), 1000)).unwrap();
+        assert_eq!(pairs.len(), 1000);
+        let params = Params::default();
+        assert!(Day04::part_one(&pairs, &params) <= Day04::part_two(&pairs, &params));
     }
 }
@@ -1,7 +1,11 @@
 use std::str::FromStr;
 
+use advent_of_code::helpers::stepper::{self, Steppable};
+use advent_of_code::{Params, ParseError, Solution};
+
 type Stack = Vec<char>;
 
+#[derive(Debug, Clone, Copy)]
 struct Move {
     origin: usize,
     end: usize,
@@ -9,21 +13,24 @@ struct Move {
 }
 
 impl FromStr for Move {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut words = s.split_ascii_whitespace();
-        words.next();
-        let count = words.next().unwrap().parse::<usize>().unwrap();
-        words.next();
-        let origin = words.next().unwrap().parse::<usize>().unwrap();
-        words.next();
-        let end = words.next().unwrap().parse::<usize>().unwrap();
+        let mut words = s.split_ascii_whitespace().skip(1).step_by(2);
+        let mut number = || -> Result<usize, ParseError> {
+            let word = words
+                .next()
+                .ok_or_else(|| ParseError::new(format!("Incomplete move: {s}")))?;
+            Ok(word.parse()?)
+        };
+        let count = number()?;
+        let origin = number()?;
+        let end = number()?;
         Ok(Self { count, origin, end })
     }
 }
 
-fn move_crates(mut stacks: Vec<Stack>, m: Move) -> Vec<Stack> {
+fn move_crates(mut stacks: Vec<Stack>, m: &Move) -> Vec<Stack> {
     for _ in 0..m.count {
         let crate_ = stacks[m.origin - 1].pop().unwrap();
         stacks[m.end - 1].push(crate_);
@@ -31,7 +38,7 @@ fn move_crates(mut stacks: Vec<Stack>, m: Move) -> Vec<Stack> {
     stacks
 }
 
-fn move_crates_at_once(mut stacks: Vec<Stack>, m: Move) -> Vec<Stack> {
+fn move_crates_at_once(mut stacks: Vec<Stack>, m: &Move) -> Vec<Stack> {
     let mut temp_stack = Vec::new();
     for _ in 0..m.count {
         let crate_ = stacks[m.origin - 1].pop().unwrap();
@@ -44,11 +51,77 @@ fn move_crates_at_once(mut stacks: Vec<Stack>, m: Move) -> Vec<Stack> {
     stacks
 }
 
-fn parse_input(input: &str) -> (Vec<Stack>, Vec<Move>) {
-    let mut halves = input.split("\n\n");
-    let stacks = halves.next().unwrap();
-    let moves = halves.next().unwrap();
-    (parse_stacks(stacks), parse_moves(moves))
+/// The stacks while the crane works through the procedure, one move per step.
+#[derive(Clone)]
+struct Crane<'a> {
+    stacks: Vec<Stack>,
+    moves: &'a [Move],
+    done: usize,
+    apply: fn(Vec<Stack>, &Move) -> Vec<Stack>,
+}
+
+impl<'a> Crane<'a> {
+    fn new(
+        stacks: &[Stack],
+        moves: &'a [Move],
+        apply: fn(Vec<Stack>, &Move) -> Vec<Stack>,
+    ) -> Self {
+        Self {
+            stacks: stacks.to_vec(),
+            moves,
+            done: 0,
+            apply,
+        }
+    }
+
+    fn top_crates(&self) -> String {
+        self.stacks
+            .iter()
+            .filter_map(|stack| stack.last())
+            .collect()
+    }
+}
+
+impl Steppable for Crane<'_> {
+    fn step(&mut self) -> bool {
+        let Some(m) = self.moves.get(self.done) else {
+            return false;
+        };
+        self.stacks = (self.apply)(std::mem::take(&mut self.stacks), m);
+        self.done += 1;
+        true
+    }
+
+    /// The stacks drawn like in the puzzle input, followed by the next move.
+    fn render(&self) -> String {
+        let height = self.stacks.iter().map(Vec::len).max().unwrap_or(0);
+        let mut lines = (0..height)
+            .rev()
+            .map(|level| {
+                self.stacks
+                    .iter()
+                    .map(|stack| match stack.get(level) {
+                        Some(c) => format!("[{c}]"),
+                        None => "   ".to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ")
+                    .trim_end()
+                    .to_string()
+            })
+            .collect::<Vec<_>>();
+        lines.push(
+            (1..=self.stacks.len())
+                .map(|i| format!(" {i} "))
+                .collect::<Vec<_>>()
+                .join(" "),
+        );
+        lines.push(match self.moves.get(self.done) {
+            Some(m) => format!("next: move {} from {} to {}", m.count, m.origin, m.end),
+            None => "done".to_string(),
+        });
+        lines.join("\n")
+    }
 }
 
 fn parse_stacks(input: &str) -> Vec<Stack> {
@@ -56,7 +129,7 @@ fn parse_stacks(input: &str) -> Vec<Stack> {
     let stacks: Vec<&str> = lines[lines.len() - 1]
         .trim()
         .split(' ')
-        .filter(|c| *c != "")
+        .filter(|c| !c.is_empty())
         .collect();
     let chars: Vec<Vec<char>> = lines.iter().map(|line| line.chars().collect()).collect();
 
@@ -76,52 +149,78 @@ fn parse_stacks(input: &str) -> Vec<Stack> {
     built_stacks
 }
 
-fn parse_moves(input: &str) -> Vec<Move> {
-    input
-        .lines()
-        .map(|line| line.parse::<Move>().unwrap())
-        .collect()
+fn parse_moves(input: &str) -> advent_of_code::Result<Vec<Move>> {
+    input.lines().map(str::parse).collect()
 }
 
-pub fn part_one(input: &str) -> Option<String> {
-    let (mut stacks, moves) = parse_input(input);
-    for m in moves {
-        stacks = move_crates(stacks, m);
-    }
+struct Day05;
 
-    let k: String = stacks.iter().map(|stack| stack.last().unwrap()).collect();
-    Some(k)
-}
+impl Solution for Day05 {
+    /// Starting stacks and the rearrangement procedure.
+    type Parsed<'a> = (Vec<Stack>, Vec<Move>);
+    type PartOne = String;
+    type PartTwo = String;
 
-pub fn part_two(input: &str) -> Option<String> {
-    let (mut stacks, moves) = parse_input(input);
-    for m in moves {
-        stacks = move_crates_at_once(stacks, m);
+    fn parse(input: &str) -> advent_of_code::Result<Self::Parsed<'_>> {
+        let (stacks, moves) = input
+            .split_once("\n\n")
+            .ok_or_else(|| ParseError::new("Missing blank line between stacks and moves"))?;
+        Ok((parse_stacks(stacks), parse_moves(moves)?))
     }
 
-    let k: String = stacks.iter().map(|stack| stack.last().unwrap()).collect();
-    Some(k)
+    fn part_one(procedure: &Self::Parsed<'_>, _: &Params) -> Option<String> {
+        let (stacks, moves) = procedure;
+        let mut crane = Crane::new(stacks, moves, move_crates);
+        stepper::step_through("05-part-one", crane.clone());
+        while crane.step() {}
+        Some(crane.top_crates())
+    }
+
+    fn part_two(procedure: &Self::Parsed<'_>, _: &Params) -> Option<String> {
+        let (stacks, moves) = procedure;
+        let mut crane = Crane::new(stacks, moves, move_crates_at_once);
+        stepper::step_through("05-part-two", crane.clone());
+        while crane.step() {}
+        Some(crane.top_crates())
+    }
 }
 
 fn main() {
     let input = &advent_of_code::read_file("inputs", 5);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(Day05, input);
 }
 
+advent_of_code::aoc_test!(
+    Day05,
+    day = 5,
+    part_one = Some("CMZ".to_string()),
+    part_two = Some("MCD".to_string())
+);
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::generate::{self, Rng};
 
     #[test]
-    fn test_part_one() {
+    fn draws_the_crane() {
         let input = advent_of_code::read_file("examples", 5);
-        assert_eq!(part_one(&input), Some("CMZ".to_string()));
+        let (stacks, moves) = Day05::parse(&input).unwrap();
+        let mut crane = Crane::new(&stacks, &moves, move_crates);
+        assert!(crane.step());
+        assert_eq!(
+            crane.render(),
+            "[D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3 \nnext: move 3 from 1 to 3"
+        );
     }
 
     #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 5);
-        assert_eq!(part_two(&input), Some("MCD".to_string()));
+    fn solves_generated_input() {
+        let (stacks, moves) = Day05::parse(&generate::day05(&mut Rng::new(0), 500)).unwrap();
+        assert_eq!(stacks.len(), 9);
+        assert_eq!(moves.len(), 500);
+        let crates = stacks.iter().map(Vec::len).sum::<usize>();
+        let top = Day05::part_one(&(stacks, moves), &Params::default()).unwrap();
+        assert!((1..=crates.min(9)).contains(&top.len()));
     }
 }
@@ -1,5 +1,7 @@
 use std::collections::HashSet;
 
+use advent_of_code::{Params, Solution};
+
 struct Streamer<'a> {
     s: &'a str,
     i: usize,
@@ -31,49 +33,61 @@ fn all_unique(s: &str) -> bool {
     set.len() == s.len()
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let size = 4;
-    let s = Streamer::new(input, size);
-    for (i, k) in s.enumerate() {
-        let i = i + size;
-        if all_unique(k) {
-            return Some(i as u32);
+struct Day06;
+
+impl Solution for Day06 {
+    type Parsed<'a> = String;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> advent_of_code::Result<Self::Parsed<'_>> {
+        Ok(input.trim().to_string())
+    }
+
+    fn part_one(datastream: &Self::Parsed<'_>, _: &Params) -> Option<u32> {
+        let size = 4;
+        let s = Streamer::new(datastream, size);
+        for (i, k) in s.enumerate() {
+            let i = i + size;
+            if all_unique(k) {
+                return Some(i as u32);
+            }
         }
+        None
     }
-    None
-}
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let size = 14;
-    let s = Streamer::new(input, size);
-    for (i, k) in s.enumerate() {
-        let i = i + size;
-        if all_unique(k) {
-            return Some(i as u32);
+    fn part_two(datastream: &Self::Parsed<'_>, _: &Params) -> Option<u32> {
+        let size = 14;
+        let s = Streamer::new(datastream, size);
+        for (i, k) in s.enumerate() {
+            let i = i + size;
+            if all_unique(k) {
+                return Some(i as u32);
+            }
         }
+        None
     }
-    None
 }
 
 fn main() {
     let input = &advent_of_code::read_file("inputs", 6);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(Day06, input);
 }
 
+advent_of_code::aoc_test!(Day06, day = 6, part_one = Some(7), part_two = Some(19));
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::generate::{self, Rng};
 
     #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 6);
-        assert_eq!(part_one(&input), Some(7));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 6);
-        assert_eq!(part_two(&input), Some(19));
+    fn solves_generated_input() {
+        let datastream = Day06::parse(&generate::day06(&mut Rng::new(0), 4096)).unwrap();
+        assert_eq!(datastream.len(), 4096);
+        let params = Params::default();
+        let message = Day06::part_two(&datastream, &params).unwrap();
+        assert!(Day06::part_one(&datastream, &params).unwrap() < message);
+        assert!(message < 4096);
     }
 }
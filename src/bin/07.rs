@@ -1,13 +1,15 @@
 use nom::{
     self,
     branch::alt,
-    bytes::complete::{is_a, tag},
-    character::complete::{alpha1, newline, u32},
+    bytes::complete::{is_not, tag},
+    character::complete::{newline, u32},
     multi::separated_list1,
-    sequence::separated_pair,
+    sequence::{preceded, separated_pair},
     IResult,
 };
 
+use advent_of_code::{Params, ParseError, Solution};
+
 #[derive(Debug, PartialEq)]
 enum Op<'a> {
     Cd(Cd<'a>),
@@ -30,7 +32,6 @@ enum FSObj<'a> {
 #[derive(Debug, Clone)]
 struct File<'a> {
     size: u32,
-    #[allow(dead_code)]
     name: &'a str,
 }
 
@@ -42,16 +43,15 @@ struct Folder<'a> {
 }
 
 impl<'a> Folder<'a> {
-    fn find(&mut self, path: &[&'a str]) -> &mut Folder<'a> {
-        if path.is_empty() {
-            return self;
-        }
-        for folder in &mut self.folders {
-            if folder.name == path[0] {
-                return folder.find(&path[1..]);
-            }
+    fn find(&mut self, path: &[&'a str]) -> Option<&mut Folder<'a>> {
+        match path.split_first() {
+            None => Some(self),
+            Some((name, rest)) => self
+                .folders
+                .iter_mut()
+                .find(|folder| folder.name == *name)?
+                .find(rest),
         }
-        panic!("No folder found at {path:?}")
     }
 
     fn size(&self) -> u32 {
@@ -116,14 +116,18 @@ impl<'a> Iterator for FolderWalker<'a> {
     }
 }
 
+/// Names may contain anything but line breaks.
+fn name(input: &str) -> IResult<&str, &str> {
+    is_not("\r\n")(input)
+}
+
 fn dir(input: &str) -> IResult<&str, FSObj> {
-    let (input, (_, name)) = separated_pair(tag("dir"), tag(" "), alpha1)(input)?;
+    let (input, name) = preceded(tag("dir "), name)(input)?;
     Ok((input, FSObj::Dir(name)))
 }
 
 fn file(input: &str) -> IResult<&str, FSObj> {
-    let (input, (size, name)) =
-        separated_pair(u32, tag(" "), is_a("abcdefghijklmnopqrstuvwxyz."))(input)?;
+    let (input, (size, name)) = separated_pair(u32, tag(" "), name)(input)?;
     Ok((input, FSObj::File { size, name }))
 }
 
@@ -137,7 +141,7 @@ fn ls(input: &str) -> IResult<&str, Op> {
 
 fn cd(input: &str) -> IResult<&str, Op> {
     let (input, _) = tag("$ cd ")(input)?;
-    let (input, cd) = alt((tag(".."), tag("/"), alpha1))(input)?;
+    let (input, cd) = name(input)?;
     let op = match cd {
         ".." => Cd::Up,
         "/" => Cd::Root,
@@ -151,56 +155,7 @@ fn commands(input: &str) -> IResult<&str, Vec<Op>> {
     Ok((input, cmd))
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    match commands(input) {
-        Ok((_, cmds)) => {
-            let mut root = Folder {
-                name: "",
-                files: vec![],
-                folders: vec![],
-            };
-            let mut current_path = Vec::new();
-            for cmd in cmds {
-                let current = root.find(&current_path[0..]);
-                match cmd {
-                    Op::Cd(Cd::Root) => {}
-                    Op::Cd(Cd::Up) => {
-                        current_path.pop();
-                    }
-                    Op::Cd(Cd::Down(dir)) => {
-                        current_path.push(dir);
-                    }
-
-                    Op::Ls(fs_objs) => {
-                        for obj in fs_objs {
-                            match obj {
-                                FSObj::File { size, name } => {
-                                    current.files.push(File { size, name })
-                                }
-                                FSObj::Dir(dir) => current.folders.push(Folder {
-                                    name: dir,
-                                    files: Vec::new(),
-                                    folders: Vec::new(),
-                                }),
-                            }
-                        }
-                    }
-                }
-            }
-            let walker = FolderWalker::new(&root);
-            Some(
-                walker
-                    .map(|dir| dir.size())
-                    .filter(|&size| size <= 100_000)
-                    .sum(),
-            )
-        }
-        Err(err) => panic!("{err}"),
-    }
-}
-
-pub fn part_two(input: &str) -> Option<u32> {
-    let (_, cmds) = commands(input).unwrap();
+fn build_tree<'a>(cmds: Vec<Op<'a>>) -> advent_of_code::Result<Folder<'a>> {
     let mut root = Folder {
         name: "",
         files: vec![],
@@ -208,9 +163,11 @@ pub fn part_two(input: &str) -> Option<u32> {
     };
     let mut current_path = Vec::new();
     for cmd in cmds {
-        let current = root.find(&current_path[0..]);
+        let current = root.find(&current_path).ok_or_else(|| {
+            ParseError::new(format!("`{}` was never listed", current_path.join("/")))
+        })?;
         match cmd {
-            Op::Cd(Cd::Root) => {}
+            Op::Cd(Cd::Root) => current_path.clear(),
             Op::Cd(Cd::Up) => {
                 current_path.pop();
             }
@@ -221,39 +178,108 @@ pub fn part_two(input: &str) -> Option<u32> {
             Op::Ls(fs_objs) => {
                 for obj in fs_objs {
                     match obj {
-                        FSObj::File { size, name } => current.files.push(File { size, name }),
-                        FSObj::Dir(dir) => current.folders.push(Folder {
-                            name: dir,
-                            files: Vec::new(),
-                            folders: Vec::new(),
-                        }),
+                        FSObj::File { size, name } => {
+                            if !current.files.iter().any(|f| f.name == name) {
+                                current.files.push(File { size, name })
+                            }
+                        }
+                        FSObj::Dir(dir) => {
+                            if !current.folders.iter().any(|f| f.name == dir) {
+                                current.folders.push(Folder {
+                                    name: dir,
+                                    files: Vec::new(),
+                                    folders: Vec::new(),
+                                })
+                            }
+                        }
                     }
                 }
             }
         }
     }
-    let walker = FolderWalker::new(&root);
-    let total_size = 70000000;
-    let required_space = 30000000;
-    let max_size = total_size - required_space;
-    let current_size = root.size();
-    let mut sizes = walker
-        .map(|dir| dir.size())
-        .filter(|size| current_size - size < max_size)
-        .collect::<Vec<u32>>();
-    sizes.sort();
-    Some(sizes[0])
+    Ok(root)
+}
+
+/// A shell log that explores every directory depth first, starting from `$ cd /`.
+#[cfg(test)]
+fn render(root: &Folder) -> String {
+    fn explore(folder: &Folder, lines: &mut Vec<String>) {
+        if !folder.files.is_empty() || !folder.folders.is_empty() {
+            lines.push("$ ls".to_string());
+        }
+        for dir in &folder.folders {
+            lines.push(format!("dir {}", dir.name));
+        }
+        for file in &folder.files {
+            lines.push(format!("{} {}", file.size, file.name));
+        }
+        for dir in &folder.folders {
+            lines.push(format!("$ cd {}", dir.name));
+            explore(dir, lines);
+            lines.push("$ cd ..".to_string());
+        }
+    }
+
+    let mut lines = vec!["$ cd /".to_string()];
+    explore(root, &mut lines);
+    lines.join("\n")
+}
+
+struct Day07;
+
+impl Solution for Day07 {
+    type Parsed<'a> = Folder<'a>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> advent_of_code::Result<Self::Parsed<'_>> {
+        let (_, cmds) = commands(input)?;
+        build_tree(cmds)
+    }
+
+    fn part_one(root: &Self::Parsed<'_>, _: &Params) -> Option<u32> {
+        let sizes = FolderWalker::new(root).map(|dir| dir.size());
+        Some(sizes.filter(|&size| size <= 100_000).sum())
+    }
+
+    fn part_two(root: &Self::Parsed<'_>, _: &Params) -> Option<u32> {
+        let total_size = 70000000;
+        let required_space = 30000000;
+        let max_size = total_size - required_space;
+        let current_size = root.size();
+        FolderWalker::new(root)
+            .map(|dir| dir.size())
+            .filter(|size| current_size - size < max_size)
+            .min()
+    }
 }
 
 fn main() {
     let input = &advent_of_code::read_file("inputs", 7);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(Day07, input);
 }
 
+advent_of_code::aoc_test!(
+    Day07,
+    day = 7,
+    part_one = Some(95437),
+    part_two = Some(24933642)
+);
+
+advent_of_code::aoc_fuzz!(
+    Day07,
+    day = 7,
+    generate = advent_of_code::generate::day07,
+    scale = 1..=30,
+    cases = 50,
+    render = render,
+    mutations = 20,
+);
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::generate::{self, Rng};
 
     #[test]
     fn parse_ls() {
@@ -264,14 +290,23 @@ dir a";
     }
 
     #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 7);
-        assert_eq!(part_one(&input), Some(95437));
+    fn solves_generated_input() {
+        let input = generate::day07(&mut Rng::new(0), 180);
+        let root = Day07::parse(&input).unwrap();
+        assert_eq!(FolderWalker::new(&root).count(), 181);
+        assert!(Day07::part_one(&root, &Params::default()).is_some());
+    }
+
+    #[test]
+    fn names_may_contain_anything() {
+        let root = Day07::parse("$ cd /\n$ ls\ndir My Docs\n$ cd My Docs\n$ ls\n12 Notes_2022.TXT")
+            .unwrap();
+        assert_eq!(root.folders[0].name, "My Docs");
+        assert_eq!(root.folders[0].files[0].name, "Notes_2022.TXT");
     }
 
     #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 7);
-        assert_eq!(part_two(&input), Some(24933642));
+    fn cd_into_unlisted_directory_is_an_error() {
+        assert!(Day07::parse("$ cd /\n$ cd a\n$ ls\n1 b").is_err());
     }
 }
@@ -1,3 +1,5 @@
+use advent_of_code::{Params, ParseError, Solution};
+
 struct Forest {
     trees: Vec<Vec<u8>>,
 }
@@ -63,63 +65,74 @@ fn visible_trees(height: u8, trees: &Vec<u8>) -> u32 {
     count
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let trees: Vec<Vec<u8>> = input
-        .lines()
-        .map(|l| l.chars().map(|c| c as u8 - b'0').collect())
-        .collect();
-    let width = trees[0].len();
-    let height = trees.len();
-    let forest = Forest { trees };
+struct Day08;
 
-    let mut count = 0;
-    for x in 0..width {
-        for y in 0..height {
-            if forest.is_tree_visible(x, y) {
-                count += 1;
+impl Solution for Day08 {
+    type Parsed<'a> = Forest;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> advent_of_code::Result<Self::Parsed<'_>> {
+        let trees = input
+            .lines()
+            .map(|l| {
+                l.chars()
+                    .map(|c| match c.to_digit(10) {
+                        Some(d) => Ok(d as u8),
+                        None => Err(ParseError::new(format!("Not a tree height: {c}"))),
+                    })
+                    .collect()
+            })
+            .collect::<advent_of_code::Result<_>>()?;
+        Ok(Forest { trees })
+    }
+
+    fn part_one(forest: &Self::Parsed<'_>, _: &Params) -> Option<u32> {
+        let width = forest.trees.first()?.len();
+        let height = forest.trees.len();
+
+        let mut count = 0;
+        for x in 0..width {
+            for y in 0..height {
+                if forest.is_tree_visible(x, y) {
+                    count += 1;
+                }
             }
         }
+        Some(count)
     }
-    Some(count)
-}
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let trees: Vec<Vec<u8>> = input
-        .lines()
-        .map(|l| l.chars().map(|c| c as u8 - b'0').collect())
-        .collect();
-    let width = trees[0].len();
-    let height = trees.len();
-    let forest = Forest { trees };
-
-    let mut current_max = u32::MIN;
-    for x in 0..width {
-        for y in 0..height {
-            current_max = current_max.max(forest.sceinic_score(x, y));
+    fn part_two(forest: &Self::Parsed<'_>, _: &Params) -> Option<u32> {
+        let width = forest.trees.first()?.len();
+        let height = forest.trees.len();
+
+        let mut current_max = u32::MIN;
+        for x in 0..width {
+            for y in 0..height {
+                current_max = current_max.max(forest.sceinic_score(x, y));
+            }
         }
+        Some(current_max)
     }
-    Some(current_max)
 }
 
 fn main() {
     let input = &advent_of_code::read_file("inputs", 8);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(Day08, input);
 }
 
+advent_of_code::aoc_test!(Day08, day = 8, part_one = Some(21), part_two = Some(8));
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::generate::{self, Rng};
 
     #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 8);
-        assert_eq!(part_one(&input), Some(21));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 8);
-        assert_eq!(part_two(&input), Some(8));
+    fn solves_generated_input() {
+        let forest = Day08::parse(&generate::day08(&mut Rng::new(0), 99)).unwrap();
+        assert_eq!(forest.trees.len(), 99);
+        // at least the trees on the edge are visible
+        assert!(Day08::part_one(&forest, &Params::default()) >= Some(4 * 98));
     }
 }
@@ -1,13 +1,18 @@
 use std::collections::HashSet;
+use std::fmt::Display;
 
 use nom::sequence::separated_pair;
 use nom::{
-    bytes::complete::{is_a, tag},
-    character::complete::{newline, u32},
+    bytes::complete::tag,
+    character::complete::{newline, one_of, u32},
     multi::separated_list1,
     IResult,
 };
 
+use advent_of_code::helpers::stepper::{self, Steppable};
+use advent_of_code::helpers::viz::{self, ColorMap};
+use advent_of_code::{Params, Solution};
+
 #[derive(Debug, Copy, Clone)]
 enum Dir {
     Up,
@@ -45,33 +50,40 @@ impl Pos {
 }
 
 fn _move(input: &str) -> IResult<&str, Move> {
-    let (input, (dir, dist)) = separated_pair(is_a("UDLR"), tag(" "), u32)(input)?;
-
-    Ok((
-        input,
-        match dir {
-            "U" => Move { dir: Dir::Up, dist },
-            "D" => Move {
-                dir: Dir::Down,
-                dist,
-            },
-            "L" => Move {
-                dir: Dir::Left,
-                dist,
-            },
-            "R" => Move {
-                dir: Dir::Right,
-                dist,
-            },
-            _ => panic!("unable to parse, {dir}"),
-        },
-    ))
+    let (input, (dir, dist)) = separated_pair(one_of("UDLR"), tag(" "), u32)(input)?;
+    let dir = match dir {
+        'U' => Dir::Up,
+        'D' => Dir::Down,
+        'L' => Dir::Left,
+        _ => Dir::Right,
+    };
+    Ok((input, Move { dir, dist }))
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let dir = match self.dir {
+            Dir::Up => 'U',
+            Dir::Down => 'D',
+            Dir::Left => 'L',
+            Dir::Right => 'R',
+        };
+        write!(f, "{dir} {}", self.dist)
+    }
+}
+
+#[cfg(test)]
+fn render(moves: &[Move]) -> String {
+    use itertools::Itertools;
+
+    moves.iter().join("\n")
 }
 
 fn moves(input: &str) -> IResult<&str, Vec<Move>> {
     separated_list1(newline, _move)(input)
 }
 
+#[derive(Clone)]
 struct HeadPath {
     moves: Vec<Move>,
     pos: Pos,
@@ -140,52 +152,175 @@ fn new_tail_pos(head: Pos, old_tail: Pos) -> Pos {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (_, moves) = moves(input).unwrap();
-    let iter = HeadPath::new(moves);
-    let mut tail_pos = Pos(0, 0);
-    let mut positions = HashSet::new();
-    for head in iter {
-        tail_pos = new_tail_pos(head, tail_pos);
-        positions.insert(tail_pos);
+/// The smallest area that contains every position of the head, and so the whole rope.
+fn bounds(moves: &[Move]) -> (Pos, Pos) {
+    HeadPath::new(moves.to_vec()).fold((Pos(0, 0), Pos(0, 0)), |(min, max), p| {
+        (
+            Pos(min.0.min(p.0), min.1.min(p.1)),
+            Pos(max.0.max(p.0), max.1.max(p.1)),
+        )
+    })
+}
+
+/// The rope as it follows the head along its path, with the positions its tail visited.
+#[derive(Clone)]
+struct Rope {
+    path: HeadPath,
+    head: Pos,
+    knots: Vec<Pos>,
+    visited: HashSet<Pos>,
+    /// The area drawn by `Display`.
+    min: Pos,
+    max: Pos,
+}
+
+impl Rope {
+    fn new(moves: &[Move], knots: usize) -> Self {
+        let (min, max) = bounds(moves);
+        Self {
+            path: HeadPath::new(moves.to_vec()),
+            head: Pos(0, 0),
+            knots: vec![Pos(0, 0); knots],
+            visited: HashSet::new(),
+            min,
+            max,
+        }
+    }
+}
+
+impl Steppable for Rope {
+    /// Moves the head by one position and lets every knot follow the one in front of it.
+    fn step(&mut self) -> bool {
+        let Some(head) = self.path.next() else {
+            return false;
+        };
+        self.head = head;
+        let mut front = head;
+        for knot in self.knots.iter_mut() {
+            *knot = new_tail_pos(front, *knot);
+            front = *knot;
+        }
+        self.visited.insert(front);
+        true
+    }
+
+    fn render(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// The rope within `min..=max`, up on top: the head `H`, knots `1` to `9`, the start `s`
+/// and the positions visited by the tail `#`.
+impl Display for Rope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in (self.min.1..=self.max.1).rev() {
+            let row = (self.min.0..=self.max.0)
+                .map(|x| {
+                    let pos = Pos(x, y);
+                    if pos == self.head {
+                        return 'H';
+                    }
+                    match self.knots.iter().position(|&knot| knot == pos) {
+                        Some(i) => char::from_digit(i as u32 + 1, 10).unwrap_or('+'),
+                        None if self.visited.contains(&pos) => '#',
+                        None if pos == Pos(0, 0) => 's',
+                        None => '.',
+                    }
+                })
+                .collect::<String>();
+            writeln!(f, "{row}")?;
+        }
+        Ok(())
     }
-    Some(positions.len() as u32)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let (_, moves) = moves(input).unwrap();
-    let iter = HeadPath::new(moves);
-    let mut rope = [Pos(0, 0); 9];
-    let mut positions = HashSet::new();
-    for head in iter {
-        rope[0] = new_tail_pos(head, rope[0]);
-        for i in 0..8 {
-            rope[i + 1] = new_tail_pos(rope[i], rope[i + 1]);
+fn rope_colors() -> ColorMap {
+    let knots = "123456789".chars().fold(ColorMap::default(), |colors, c| {
+        colors.with(c, [230, 190, 80])
+    });
+    knots
+        .with('H', [220, 60, 60])
+        .with('#', [70, 90, 140])
+        .with('s', [80, 200, 120])
+}
+
+struct Day09;
+
+impl Solution for Day09 {
+    type Parsed<'a> = Vec<Move>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> advent_of_code::Result<Self::Parsed<'_>> {
+        let (_, moves) = moves(input)?;
+        Ok(moves)
+    }
+
+    fn part_one(moves: &Self::Parsed<'_>, _: &Params) -> Option<u32> {
+        let mut rope = Rope::new(moves, 1);
+        stepper::step_through("09-part-one", rope.clone());
+        while rope.step() {}
+        Some(rope.visited.len() as u32)
+    }
+
+    fn part_two(moves: &Self::Parsed<'_>, _: &Params) -> Option<u32> {
+        let mut rope = Rope::new(moves, 9);
+        stepper::step_through("09-part-two", rope.clone());
+        let mut recorder = viz::record("09-part-two", rope_colors());
+        while rope.step() {
+            if let Some(recorder) = &mut recorder {
+                recorder.push_chars(&rope);
+            }
+        }
+        if let Some(recorder) = recorder {
+            recorder.finish();
         }
-        positions.insert(rope[8]);
+        Some(rope.visited.len() as u32)
     }
-    Some(positions.len() as u32)
 }
 
 fn main() {
     let input = &advent_of_code::read_file("inputs", 9);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(Day09, input);
 }
 
+advent_of_code::aoc_test!(
+    Day09,
+    example { day = 9, part_one = Some(13), part_two = Some(1) },
+    larger { input = "R 5\nU 8\nL 8\nD 3\nR 17\nD 10\nL 25\nU 20", part_two = Some(36) },
+);
+
+advent_of_code::aoc_fuzz!(
+    Day09,
+    day = 9,
+    generate = advent_of_code::generate::day09,
+    scale = 1..=50,
+    cases = 50,
+    render = render,
+    mutations = 20,
+);
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::generate::{self, Rng};
 
     #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 9);
-        assert_eq!(part_one(&input), Some(13));
+    fn draws_the_rope() {
+        let moves = Day09::parse("R 2\nU 1").unwrap();
+        let mut rope = Rope::new(&moves, 2);
+        assert_eq!(rope.to_string(), "...\nH..\n");
+        while rope.step() {}
+        assert_eq!(rope.to_string(), "..H\n21.\n");
     }
 
     #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 9);
-        assert_eq!(part_two(&input), Some(1));
+    fn solves_generated_input() {
+        let moves = Day09::parse(&generate::day09(&mut Rng::new(0), 2000)).unwrap();
+        assert_eq!(moves.len(), 2000);
+        assert!(
+            Day09::part_one(&moves, &Params::default())
+                >= Day09::part_two(&moves, &Params::default())
+        );
     }
 }
@@ -1,4 +1,6 @@
 #![feature(iter_intersperse)]
+use std::fmt::Display;
+
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -8,6 +10,9 @@ use nom::{
     IResult,
 };
 
+use advent_of_code::helpers::stepper::{self, Steppable};
+use advent_of_code::{Params, Solution};
+
 #[derive(Debug, Clone)]
 enum Instruction {
     Noop,
@@ -24,16 +29,43 @@ fn addx(input: &str) -> IResult<&str, Instruction> {
     Ok((input, Instruction::Addx(v)))
 }
 
+impl Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Instruction::Noop => write!(f, "noop"),
+            Instruction::Addx(v) => write!(f, "addx {v}"),
+        }
+    }
+}
+
+#[cfg(test)]
+fn render(instrs: &[Instruction]) -> String {
+    instrs
+        .iter()
+        .map(Instruction::to_string)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 fn instructions(input: &str) -> IResult<&str, Vec<Instruction>> {
     separated_list1(newline, alt((noop, addx)))(input)
 }
 
+#[derive(Clone)]
 struct Processor {
     n_cycles: u32,
     intructions: Vec<Instruction>,
     state: i32,
 }
 impl Processor {
+    fn new(instrs: &[Instruction]) -> Self {
+        Self {
+            n_cycles: 0,
+            intructions: instrs.to_vec(),
+            state: 1,
+        }
+    }
+
     fn tick(&mut self) {
         match self.intructions.get(0) {
             Some(inst) => match inst {
@@ -49,6 +81,32 @@ impl Processor {
     }
 }
 
+impl Steppable for Processor {
+    fn step(&mut self) -> bool {
+        if self.intructions.is_empty() {
+            return false;
+        }
+        self.tick();
+        true
+    }
+
+    fn render(&self) -> String {
+        let next = match self.intructions.first() {
+            Some(inst) => inst.to_string(),
+            None => "done".to_string(),
+        };
+        format!("cycle {}: X={}\nnext: {next}", self.n_cycles, self.state)
+    }
+
+    /// `cycle N` holds from the `N`th cycle on, anything else is looked up in the rendering.
+    fn check(&self, condition: &str) -> bool {
+        match condition.strip_prefix("cycle ").map(str::parse::<u32>) {
+            Some(Ok(cycle)) => self.n_cycles >= cycle,
+            _ => self.render().contains(condition),
+        }
+    }
+}
+
 fn state_at(states: &Vec<(u32, i32)>, at: u32) -> i32 {
     states
         .iter()
@@ -58,89 +116,102 @@ fn state_at(states: &Vec<(u32, i32)>, at: u32) -> i32 {
         .1
 }
 
-fn cycle_n_at(n: u32) -> u32 {
-    20 + n * 40
-}
-
-pub fn part_one(input: &str) -> Option<i32> {
-    let (_, instrs) = instructions(input).unwrap();
-    let mut p = Processor {
-        n_cycles: 0,
-        intructions: instrs,
-        state: 1,
-    };
+fn register_states(instrs: &[Instruction]) -> Vec<(u32, i32)> {
+    let mut p = Processor::new(instrs);
     let mut states = Vec::new();
     states.push((p.n_cycles, p.state));
     while !p.intructions.is_empty() {
         p.tick();
         states.push((p.n_cycles, p.state));
     }
-    let total_cycles = states[states.len() - 1].0;
-    let mut i = 0;
-    let mut total = 0;
-    while cycle_n_at(i) < total_cycles {
-        let cycles = cycle_n_at(i);
-        total += state_at(&states, cycles) * cycles as i32;
-        i += 1;
-    }
-    Some(total)
+    states
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let (_, instrs) = instructions(input).unwrap();
-    let mut p = Processor {
-        n_cycles: 0,
-        intructions: instrs,
-        state: 1,
-    };
-    let mut states = Vec::new();
-    states.push((p.n_cycles, p.state));
-    while !p.intructions.is_empty() {
-        p.tick();
-        states.push((p.n_cycles, p.state));
+fn cycle_n_at(n: u32) -> u32 {
+    20 + n * 40
+}
+
+struct Day10;
+
+impl Solution for Day10 {
+    type Parsed<'a> = Vec<Instruction>;
+    type PartOne = i32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> advent_of_code::Result<Self::Parsed<'_>> {
+        let (_, instrs) = instructions(input)?;
+        Ok(instrs)
     }
-    let mut screen = Vec::new();
-    for row in 0..6 {
-        let mut pixel_row = Vec::new();
-        for column in 0..40 {
-            let i = 40 * row + column;
-            let state = state_at(&states, i);
-            println!("State: {state}, Position: {i}");
-            pixel_row.push(match column as i32 - state {
-                -1..=1 => '#',
-                _ => ' ',
-            });
+
+    fn part_one(instrs: &Self::Parsed<'_>, _: &Params) -> Option<i32> {
+        stepper::step_through("10", Processor::new(instrs));
+        let states = register_states(instrs);
+        let total_cycles = states[states.len() - 1].0;
+        let mut i = 0;
+        let mut total = 0;
+        while cycle_n_at(i) < total_cycles {
+            let cycles = cycle_n_at(i);
+            total += state_at(&states, cycles) * cycles as i32;
+            i += 1;
         }
-        screen.push(pixel_row);
+        Some(total)
+    }
+
+    fn part_two(instrs: &Self::Parsed<'_>, _: &Params) -> Option<u32> {
+        let states = register_states(instrs);
+        let mut screen = Vec::new();
+        for row in 0..6 {
+            let mut pixel_row = Vec::new();
+            for column in 0..40 {
+                let i = 40 * row + column;
+                let state = state_at(&states, i);
+                advent_of_code::aoc_trace!("State: {state}, Position: {i}");
+                pixel_row.push(match column as i32 - state {
+                    -1..=1 => '#',
+                    _ => ' ',
+                });
+            }
+            screen.push(pixel_row);
+        }
+        let s = screen
+            .iter()
+            .map(|row| row.iter().collect::<String>())
+            .intersperse('\n'.to_string())
+            .collect::<String>();
+        println!("{s}");
+        None
     }
-    let s = screen
-        .iter()
-        .map(|row| row.iter().collect::<String>())
-        .intersperse('\n'.to_string())
-        .collect::<String>();
-    println!("{s}");
-    None
 }
 
 fn main() {
     let input = &advent_of_code::read_file("inputs", 10);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(Day10, input);
 }
 
+advent_of_code::aoc_test!(Day10, day = 10, part_one = Some(13360), part_two = None);
+
+advent_of_code::aoc_fuzz!(
+    Day10,
+    day = 10,
+    generate = advent_of_code::generate::day10,
+    scale = 1..=50,
+    cases = 50,
+    render = render,
+    mutations = 20,
+);
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::helpers::stepper::Stepper;
 
     #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 10);
-        assert_eq!(part_one(&input), Some(13360));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 10);
-        assert_eq!(part_two(&input), None);
+    fn steps_through_the_program() {
+        let instrs = Day10::parse("noop\naddx 3\naddx -5").unwrap();
+        let mut stepper = Stepper::new(Processor::new(&instrs));
+        assert!(stepper.run_until(|p| p.check("cycle 3")));
+        assert_eq!(stepper.current().render(), "cycle 3: X=4\nnext: addx -5");
+        assert!(stepper.run_until(|p| p.check("X=-1")));
+        assert!(!stepper.forward());
     }
 }
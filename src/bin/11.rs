@@ -1,10 +1,15 @@
+use advent_of_code::helpers::math::lcm_all;
+use advent_of_code::{Params, ParseError, Solution};
+use itertools::Itertools;
 use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::{newline, u32, u64},
+    combinator::value,
     multi::separated_list1,
     IResult,
 };
+use std::fmt::Display;
 
 fn monkey_id(input: &str) -> IResult<&str, u32> {
     let (input, _) = tag("Monkey ")(input)?;
@@ -52,15 +57,7 @@ fn const_(input: &str) -> IResult<&str, Val> {
 }
 
 fn op(input: &str) -> IResult<&str, Op> {
-    let (input, op) = alt((tag("* "), tag("+ ")))(input)?;
-    Ok((
-        input,
-        match op {
-            "* " => Op::Times,
-            "+ " => Op::Plus,
-            _ => panic!("couldn't parse: {op}"),
-        },
-    ))
+    alt((value(Op::Times, tag("* ")), value(Op::Plus, tag("+ "))))(input)
 }
 
 fn operation(input: &str) -> IResult<&str, Expr> {
@@ -99,9 +96,8 @@ fn test(input: &str) -> IResult<&str, Test> {
     ))
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Monkey {
-    #[allow(dead_code)]
     id: u32,
     items: Vec<u64>,
     operation: Expr,
@@ -110,10 +106,10 @@ struct Monkey {
 }
 
 fn monkey(input: &str) -> IResult<&str, Monkey> {
-    let (input, id) = monkey_id(input).unwrap();
-    let (input, ids) = starting_items(input).unwrap();
-    let (input, op) = operation(input).unwrap();
-    let (input, num) = test(input).unwrap();
+    let (input, id) = monkey_id(input)?;
+    let (input, ids) = starting_items(input)?;
+    let (input, op) = operation(input)?;
+    let (input, num) = test(input)?;
 
     Ok((
         input,
@@ -127,6 +123,34 @@ fn monkey(input: &str) -> IResult<&str, Monkey> {
     ))
 }
 
+impl Display for Monkey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let op = match self.operation.op {
+            Op::Times => '*',
+            Op::Plus => '+',
+        };
+        let right = match self.operation.right {
+            Val::Old => "old".to_string(),
+            Val::Const(c) => c.to_string(),
+        };
+        writeln!(f, "Monkey {}:", self.id)?;
+        writeln!(f, "  Starting items: {}", self.items.iter().join(", "))?;
+        writeln!(f, "  Operation: new = old {op} {right}")?;
+        writeln!(f, "  Test: divisible by {}", self.test.divisible_by)?;
+        writeln!(f, "    If true: throw to monkey {}", self.test.true_monkey)?;
+        write!(
+            f,
+            "    If false: throw to monkey {}",
+            self.test.false_monkey
+        )
+    }
+}
+
+#[cfg(test)]
+fn render(monkeys: &[Monkey]) -> String {
+    monkeys.iter().join("\n\n")
+}
+
 fn evaluate(old: u128, expr: Expr) -> u128 {
     match (expr.op, expr.right) {
         (Op::Times, Val::Old) => old * old,
@@ -168,72 +192,87 @@ fn process_monkey_2(i: usize, monkeys: &mut Vec<Monkey>, modulus: u128) {
     }
 }
 
-fn lcm(a: u32, b: u32) -> u32 {
-    a * b / gcd(a, b)
-}
+struct Day11;
+
+impl Solution for Day11 {
+    type Parsed<'a> = Vec<Monkey>;
+    type PartOne = u32;
+    type PartTwo = u64;
 
-fn gcd(a: u32, b: u32) -> u32 {
-    let mut a = a;
-    let mut b = b;
-    while a % b != 0 {
-        let other = a % b;
-        a = b;
-        b = other;
+    fn parse(input: &str) -> advent_of_code::Result<Self::Parsed<'_>> {
+        let (_, monkeys) = separated_list1(tag("\n\n"), monkey)(input)?;
+        for monkey in &monkeys {
+            let targets = [monkey.test.true_monkey, monkey.test.false_monkey];
+            if targets
+                .iter()
+                .any(|&target| target as usize >= monkeys.len())
+            {
+                return Err(ParseError::new(format!(
+                    "Monkey {} throws to a monkey that doesn't exist",
+                    monkey.id
+                )));
+            }
+            if monkey.test.divisible_by == 0 {
+                return Err(ParseError::new(format!(
+                    "Monkey {} tests for divisibility by 0",
+                    monkey.id
+                )));
+            }
+        }
+        Ok(monkeys)
     }
-    b
-}
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (_, mut monkeys) = separated_list1(tag("\n\n"), monkey)(input).unwrap();
-    for _ in 0..20 {
-        for i in 0..monkeys.len() {
-            process_monkey(i, &mut monkeys);
+    fn part_one(monkeys: &Self::Parsed<'_>, _: &Params) -> Option<u32> {
+        let mut monkeys = monkeys.clone();
+        for _ in 0..20 {
+            for i in 0..monkeys.len() {
+                process_monkey(i, &mut monkeys);
+            }
         }
+        let mut inspections: Vec<u32> = monkeys.iter().map(|monkey| monkey.inspections).collect();
+        inspections.sort();
+        let len = inspections.len();
+        Some(inspections[len - 1] * inspections[len - 2])
     }
-    let mut inspections: Vec<u32> = monkeys.iter().map(|monkey| monkey.inspections).collect();
-    inspections.sort();
-    let len = inspections.len();
-    Some(inspections[len - 1] * inspections[len - 2])
-}
-
-pub fn part_two(input: &str) -> Option<u64> {
-    let (_, mut monkeys) = separated_list1(tag("\n\n"), monkey)(input).unwrap();
-    let modulus = monkeys
-        .iter()
-        .map(|monkey| monkey.test.divisible_by)
-        .reduce(lcm)
-        .unwrap();
-    println!("Modulus is {modulus}");
-    for _ in 0..10000 {
-        for i in 0..monkeys.len() {
-            process_monkey_2(i, &mut monkeys, modulus as u128);
+
+    fn part_two(monkeys: &Self::Parsed<'_>, _: &Params) -> Option<u64> {
+        let mut monkeys = monkeys.clone();
+        let modulus = lcm_all(
+            monkeys
+                .iter()
+                .map(|monkey| monkey.test.divisible_by as u128),
+        )?;
+        advent_of_code::aoc_debug!("Modulus is {modulus}");
+        for _ in 0..10000 {
+            for i in 0..monkeys.len() {
+                process_monkey_2(i, &mut monkeys, modulus);
+            }
         }
+        let mut inspections: Vec<u32> = monkeys.iter().map(|monkey| monkey.inspections).collect();
+        inspections.sort();
+        let len = inspections.len();
+        Some(inspections[len - 1] as u64 * inspections[len - 2] as u64)
     }
-    let mut inspections: Vec<u32> = monkeys.iter().map(|monkey| monkey.inspections).collect();
-    inspections.sort();
-    let len = inspections.len();
-    Some(inspections[len - 1] as u64 * inspections[len - 2] as u64)
 }
 
 fn main() {
     let input = &advent_of_code::read_file("inputs", 11);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(Day11, input);
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 11);
-        assert_eq!(part_one(&input), Some(10605));
-    }
+advent_of_code::aoc_test!(
+    Day11,
+    day = 11,
+    part_one = Some(10605),
+    part_two = Some(2713310158)
+);
 
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 11);
-        assert_eq!(part_two(&input), Some(2713310158));
-    }
-}
+advent_of_code::aoc_fuzz!(
+    Day11,
+    day = 11,
+    generate = advent_of_code::generate::day11,
+    scale = 2..=8,
+    cases = 50,
+    render = render,
+    mutations = 20,
+);
@@ -3,6 +3,8 @@ use std::{
     str::FromStr,
 };
 
+use advent_of_code::{Params, ParseError, Solution};
+
 struct Grid {
     points: Vec<Vec<char>>,
 }
@@ -107,7 +109,7 @@ fn height(c: char) -> u8 {
 }
 
 impl FromStr for Grid {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let k = s
@@ -157,51 +159,61 @@ fn a_star(grid: &Grid, start: Pos, goal: Pos) -> Option<u32> {
     known.get(0).map(|k| path_length.get(k).unwrap()).copied()
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let grid = input.parse::<Grid>().unwrap();
-    let k = a_star(&grid, grid.start(), grid.end()).unwrap();
-    println!("{k:?}");
-    Some(k)
-}
+struct Day12;
+
+impl Solution for Day12 {
+    type Parsed<'a> = Grid;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> advent_of_code::Result<Self::Parsed<'_>> {
+        input.parse()
+    }
+
+    fn part_one(grid: &Self::Parsed<'_>, _: &Params) -> Option<u32> {
+        let k = a_star(&grid, grid.start(), grid.end()).unwrap();
+        Some(k)
+    }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let grid = input.parse::<Grid>().unwrap();
-    let mut starting_points = Vec::new();
-    for (y, row) in grid.points.iter().enumerate() {
-        for (x, &char) in row.iter().enumerate() {
-            if char == 'a' || char == 'S' {
-                starting_points.push(Pos { x, y });
+    fn part_two(grid: &Self::Parsed<'_>, _: &Params) -> Option<u32> {
+        let mut starting_points = Vec::new();
+        for (y, row) in grid.points.iter().enumerate() {
+            for (x, &char) in row.iter().enumerate() {
+                if char == 'a' || char == 'S' {
+                    starting_points.push(Pos { x, y });
+                }
             }
         }
-    }
-    let mut dists: Vec<u32> = starting_points
-        .iter()
-        .filter_map(|start| a_star(&grid, *start, grid.end()))
-        .collect();
-    dists.sort();
+        let mut dists: Vec<u32> = starting_points
+            .iter()
+            .filter_map(|start| a_star(&grid, *start, grid.end()))
+            .collect();
+        dists.sort();
 
-    Some(dists[0])
+        Some(dists[0])
+    }
 }
 
 fn main() {
     let input = &advent_of_code::read_file("inputs", 12);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(Day12, input);
 }
 
+advent_of_code::aoc_test!(Day12, day = 12, part_one = Some(31), part_two = Some(29));
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::generate::{self, Rng};
 
     #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 12);
-        assert_eq!(part_one(&input), Some(31));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 12);
-        assert_eq!(part_two(&input), Some(29));
+    fn solves_generated_input() {
+        // the search is slow, so the map is smaller than a real one
+        let grid = Day12::parse(&generate::day12(&mut Rng::new(0), 10)).unwrap();
+        assert_eq!(grid.points.len(), 10);
+        let params = Params::default();
+        let steps = Day12::part_one(&grid, &params).unwrap();
+        assert!(steps >= 25);
+        assert!(Day12::part_two(&grid, &params).unwrap() <= steps);
     }
 }
@@ -1,4 +1,5 @@
 use std::cmp::Ordering;
+use std::fmt::Display;
 
 use nom::{
     branch::alt,
@@ -9,6 +10,8 @@ use nom::{
     IResult,
 };
 
+use advent_of_code::{Params, Solution};
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 enum PacketItem {
     Value(u32),
@@ -59,6 +62,25 @@ impl Ord for PacketItem {
     }
 }
 
+impl Display for Packet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let items = self.items.iter().map(|item| match item {
+            PacketItem::Value(v) => v.to_string(),
+            PacketItem::SubPacket(p) => p.to_string(),
+        });
+        write!(f, "[{}]", items.collect::<Vec<_>>().join(","))
+    }
+}
+
+#[cfg(test)]
+fn render(pairs: &[PacketPair]) -> String {
+    pairs
+        .iter()
+        .map(|pair| format!("{}\n{}", pair.left, pair.right))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
 fn packet_value(input: &str) -> IResult<&str, PacketItem> {
     let (input, packet_i) = complete::u32(input)?;
     Ok((input, PacketItem::Value(packet_i)))
@@ -87,46 +109,72 @@ fn all_packet_pairs(input: &str) -> IResult<&str, Vec<PacketPair>> {
     separated_list1(multispace1, packet_pair)(input)
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (_, pairs) = all_packet_pairs(input).unwrap();
-    let k: usize = pairs
-        .iter()
-        .enumerate()
-        .map(|p| (p.0 + 1, p.1))
-        .map(|(i, pair)| (i, pair.left < pair.right))
-        .filter(|(_, correct)| *correct)
-        .map(|(i, _)| i)
-        .inspect(|i| println!("{i}"))
-        .sum();
-
-    Some(k as u32)
-}
+struct Day13;
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let (_, packets) = separated_list1(multispace1, packet)(input).unwrap();
-    let (_, additional_packets) = separated_list1(tag(","), packet)("[[2]],[[6]]").unwrap();
-    let mut packets = [packets, additional_packets.clone()].concat();
-    packets.sort();
-    let k: usize = packets
-        .iter()
-        .enumerate()
-        .filter_map(|(i, p)| {
-            if *p == additional_packets[0] || *p == additional_packets[1] {
-                Some(i + 1)
-            } else {
-                None
-            }
-        })
-        .product();
-    Some(k as u32)
+impl Solution for Day13 {
+    type Parsed<'a> = Vec<PacketPair>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> advent_of_code::Result<Self::Parsed<'_>> {
+        let (_, pairs) = all_packet_pairs(input)?;
+        Ok(pairs)
+    }
+
+    fn part_one(pairs: &Self::Parsed<'_>, _: &Params) -> Option<u32> {
+        let k: usize = pairs
+            .iter()
+            .enumerate()
+            .map(|p| (p.0 + 1, p.1))
+            .map(|(i, pair)| (i, pair.left < pair.right))
+            .filter(|(_, correct)| *correct)
+            .map(|(i, _)| i)
+            .inspect(|i| advent_of_code::aoc_trace!("Pair {i} is in the right order"))
+            .sum();
+
+        Some(k as u32)
+    }
+
+    fn part_two(pairs: &Self::Parsed<'_>, _: &Params) -> Option<u32> {
+        let packets = pairs
+            .iter()
+            .flat_map(|pair| [pair.left.clone(), pair.right.clone()])
+            .collect::<Vec<_>>();
+        let (_, additional_packets) = separated_list1(tag(","), packet)("[[2]],[[6]]").unwrap();
+        let mut packets = [packets, additional_packets.clone()].concat();
+        packets.sort();
+        let k: usize = packets
+            .iter()
+            .enumerate()
+            .filter_map(|(i, p)| {
+                if *p == additional_packets[0] || *p == additional_packets[1] {
+                    Some(i + 1)
+                } else {
+                    None
+                }
+            })
+            .product();
+        Some(k as u32)
+    }
 }
 
 fn main() {
     let input = &advent_of_code::read_file("inputs", 13);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(Day13, input);
 }
 
+advent_of_code::aoc_test!(Day13, day = 13, part_one = Some(13), part_two = Some(140));
+
+advent_of_code::aoc_fuzz!(
+    Day13,
+    day = 13,
+    generate = advent_of_code::generate::day13,
+    scale = 1..=20,
+    cases = 50,
+    render = render,
+    mutations = 20,
+);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -152,16 +200,4 @@ mod tests {
             ))
         )
     }
-
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 13);
-        assert_eq!(part_one(&input), Some(13));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 13);
-        assert_eq!(part_two(&input), Some(140));
-    }
 }
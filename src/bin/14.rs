@@ -8,36 +8,74 @@ use nom::{
     IResult,
 };
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (input, points) = all_line_segments(input).unwrap();
-    assert!(input.is_empty());
-    let mut scene = Scene::new(points).unwrap();
-    let mut count = 0;
-    while scene.drop_sand() {
-        count += 1;
+use advent_of_code::helpers::stepper::{self, Steppable};
+use advent_of_code::helpers::viz::{self, ColorMap};
+use advent_of_code::{Params, ParseError, Solution};
+
+struct Day14;
+
+impl Solution for Day14 {
+    /// Rock paths, each a list of line segment corners.
+    type Parsed<'a> = Vec<Vec<Point>>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> advent_of_code::Result<Self::Parsed<'_>> {
+        let (rest, lines) = all_line_segments(input)?;
+        if !rest.trim().is_empty() {
+            return Err(ParseError::new(format!("Unexpected input: {rest}")));
+        }
+        Ok(lines)
+    }
+
+    fn part_one(lines: &Self::Parsed<'_>, _: &Params) -> Option<u32> {
+        Some(pour(Scene::new(lines.clone())?, "14-part-one"))
+    }
+
+    fn part_two(lines: &Self::Parsed<'_>, _: &Params) -> Option<u32> {
+        Some(pour(Scene::new_2(lines.clone())?, "14-part-two"))
     }
-    println!("{scene}");
-    Some(count)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let (input, points) = all_line_segments(input).unwrap();
-    assert!(input.is_empty());
-    let mut scene = Scene::new_2(points).unwrap();
+/// Drops sand until it comes to rest nowhere, recording the scene after every unit.
+/// With `--step`, steps through the units first.
+fn pour(mut scene: Scene, name: &str) -> u32 {
+    let colors = ColorMap::default()
+        .with('o', [230, 190, 80])
+        .with('+', [220, 60, 60]);
+    stepper::step_through(name, scene.clone());
+    let mut recorder = viz::record(name, colors);
     let mut count = 0;
     while scene.drop_sand() {
         count += 1;
+        if let Some(recorder) = &mut recorder {
+            recorder.push_chars(&scene);
+        }
+    }
+    advent_of_code::aoc_debug!("{scene}");
+    if let Some(recorder) = recorder {
+        recorder.finish();
     }
-    println!("{scene}");
-    Some(count)
+    count
 }
 
 fn main() {
     let input = &advent_of_code::read_file("inputs", 14);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(Day14, input);
 }
 
+advent_of_code::aoc_test!(Day14, day = 14, part_one = Some(24), part_two = Some(93));
+
+advent_of_code::aoc_fuzz!(
+    Day14,
+    day = 14,
+    generate = advent_of_code::generate::day14,
+    scale = 1..=20,
+    cases = 50,
+    render = render,
+    mutations = 20,
+);
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 struct Point {
     x: u32,
@@ -50,7 +88,7 @@ enum Material {
     Sand,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Scene {
     min_x: u32,
     max_x: u32,
@@ -199,6 +237,17 @@ impl Scene {
     }
 }
 
+/// One unit of sand per step.
+impl Steppable for Scene {
+    fn step(&mut self) -> bool {
+        self.drop_sand()
+    }
+
+    fn render(&self) -> String {
+        self.to_string()
+    }
+}
+
 fn points_between(start: &Point, end: &Point) -> Option<Vec<Point>> {
     if start.x == end.x {
         let mut ret = Vec::new();
@@ -264,6 +313,16 @@ fn all_line_segments(input: &str) -> IResult<&str, Vec<Vec<Point>>> {
     separated_list1(newline, line_segments)(input)
 }
 
+#[cfg(test)]
+fn render(lines: &[Vec<Point>]) -> String {
+    use itertools::Itertools;
+
+    lines
+        .iter()
+        .map(|line| line.iter().map(|p| format!("{},{}", p.x, p.y)).join(" -> "))
+        .join("\n")
+}
+
 fn diff(a: u32, b: u32) -> u32 {
     a.max(b) - a.min(b)
 }
@@ -271,6 +330,15 @@ fn diff(a: u32, b: u32) -> u32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::generate::{self, Rng};
+
+    #[test]
+    fn solves_generated_input() {
+        let lines = Day14::parse(&generate::day14(&mut Rng::new(0), 150)).unwrap();
+        assert_eq!(lines.len(), 150);
+        let params = Params::default();
+        assert!(Day14::part_one(&lines, &params) < Day14::part_two(&lines, &params));
+    }
 
     #[test]
     fn drop_sand() {
@@ -322,16 +390,4 @@ mod tests {
         let res = point(input);
         assert_eq!(res, Ok(("", Point { x: 456, y: 12 })));
     }
-
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 14);
-        assert_eq!(part_one(&input), Some(24));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 14);
-        assert_eq!(part_two(&input), Some(93));
-    }
 }
@@ -1,5 +1,7 @@
-use std::collections::{BTreeSet, HashSet};
-
+use advent_of_code::differential::Reference;
+use advent_of_code::helpers::interval::{Interval, IntervalSet};
+use advent_of_code::{Param, Params, Solution};
+use itertools::Itertools;
 use nom::{bytes::complete::tag, character::complete, IResult};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -9,15 +11,15 @@ struct Point {
 }
 
 impl Point {
-    fn distance(&self, other: &Self) -> u32 {
-        (self.x - other.x).abs() as u32 + (self.y - other.y).abs() as u32
+    fn distance(&self, other: &Self) -> u64 {
+        self.x.abs_diff(other.x) as u64 + self.y.abs_diff(other.y) as u64
     }
 }
 
 struct Sensor {
     loc: Point,
     nearest_beacon: Point,
-    range: u32,
+    range: u64,
 }
 
 impl Sensor {
@@ -28,13 +30,11 @@ impl Sensor {
             range: loc.distance(&nearest_beacon),
         }
     }
-    fn is_in_range(&self, point: Point) -> bool {
-        let dist = self.loc.distance(&point);
-        dist <= self.range
-    }
 
-    fn covers(&self, rect: Rect) -> bool {
-        rect.points().iter().all(|point| self.is_in_range(*point))
+    /// The x positions on row `y` that are within range of this sensor.
+    fn row_coverage(&self, y: i32) -> Option<Interval> {
+        let spare = self.range as i64 - self.loc.y.abs_diff(y) as i64;
+        Interval::new(self.loc.x as i64 - spare, self.loc.x as i64 + spare)
     }
 }
 
@@ -55,220 +55,131 @@ fn sensor(input: &str) -> IResult<&str, Sensor> {
     Ok((input, Sensor::new(loc, nearest_beacon)))
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let line = 10;
-    // let line = 2000000;
-    let sensors: Vec<Sensor> = input.lines().map(|line| sensor(line).unwrap().1).collect();
-    let min_x = sensors
-        .iter()
-        .map(|s| s.loc.x - s.range as i32)
-        .min()
-        .unwrap();
-    let max_x = sensors
+#[cfg(test)]
+fn render(sensors: &[Sensor]) -> String {
+    sensors
         .iter()
-        .map(|s| s.loc.x + s.range as i32)
-        .max()
-        .unwrap();
-
-    let mut count = 0;
-    'outer: for x in min_x..=max_x {
-        let point = Point { x, y: line };
-        for sensor in sensors.iter() {
-            if sensor.nearest_beacon == point {
-                continue 'outer;
-            }
-        }
-        for sensor in sensors.iter() {
-            if sensor.is_in_range(point) {
-                count += 1;
-                break;
-            }
-        }
-    }
-
-    Some(count)
+        .map(|s| {
+            let (loc, beacon) = (s.loc, s.nearest_beacon);
+            format!(
+                "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+                loc.x, loc.y, beacon.x, beacon.y
+            )
+        })
+        .join("\n")
 }
 
-pub fn part_two(input: &str) -> Option<u128> {
-    let sensors: Vec<Sensor> = input.lines().map(|line| sensor(line).unwrap().1).collect();
-    let range = if false { 4_000_000 } else { 20 };
-
-    let mut rects = vec![Rect::new(0, range + 1, 0, range + 1).unwrap()];
-    while !rects.is_empty() {
-        let rect = rects.pop().unwrap();
-        if sensors.iter().all(|sensor| !sensor.covers(rect)) {
-            match rect.subdivisions() {
-                Some(sub_rects) => rects = [rects, sub_rects].concat(),
-                None => {
-                    let point = rect.points()[0];
-                    println!("{point:?}");
-                    return Some(point.x as u128 * 4_000_000 + point.y as u128);
-                }
-            }
-        }
-    }
-
-    None
+/// All x positions on row `y` that are in range of at least one sensor.
+fn coverage(sensors: &[Sensor], y: i32) -> IntervalSet {
+    sensors.iter().filter_map(|s| s.row_coverage(y)).collect()
 }
 
-fn main() {
-    let rect = Rect::new(0, 8, 0, 8).unwrap();
-    dbg!("{:?}", rect.subdivisions().unwrap());
-    let input = &advent_of_code::read_file("inputs", 15);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
-}
+struct Day15;
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct Rect {
-    min_x: i32,
-    max_x: i32,
-    min_y: i32,
-    max_y: i32,
-}
+impl Solution for Day15 {
+    type Parsed<'a> = Vec<Sensor>;
+    type PartOne = u32;
+    type PartTwo = u128;
 
-impl Rect {
-    fn new(min_x: i32, max_x: i32, min_y: i32, max_y: i32) -> Option<Self> {
-        if (max_x <= min_x) || (max_y <= min_y) {
-            None
-        } else {
-            Some(Self {
-                min_x,
-                max_x,
-                min_y,
-                max_y,
-            })
-        }
-    }
+    const PARAMS: &'static [Param] = &[
+        Param::new("row", 10, 2_000_000),
+        Param::new("bound", 20, 4_000_000),
+    ];
 
-    fn points(self) -> Vec<Point> {
-        let ps = vec![
-            Point {
-                x: self.min_x,
-                y: self.min_y,
-            },
-            Point {
-                x: self.min_x,
-                y: self.max_y - 1,
-            },
-            Point {
-                x: self.max_x - 1,
-                y: self.min_y,
-            },
-            Point {
-                x: self.max_x - 1,
-                y: self.max_y - 1,
-            },
-        ];
-        let set: HashSet<Point> = HashSet::from_iter(ps.into_iter());
-        set.into_iter().collect()
+    fn parse(input: &str) -> advent_of_code::Result<Self::Parsed<'_>> {
+        input.lines().map(|line| Ok(sensor(line)?.1)).collect()
     }
 
-    fn subdivisions(self) -> Option<Vec<Rect>> {
-        let dx = self.max_x - self.min_x;
-        let dy = self.max_y - self.min_y;
-        let hx = dx / 2;
-        let hy = dy / 2;
-        match (dx, dy) {
-            (1, 1) => None,
-            (2.., 1) => Some(vec![
-                Rect::new(self.min_x, self.min_x + hx, self.min_y, self.max_y).unwrap(),
-                Rect::new(self.min_x + hx, self.max_x, self.min_y, self.max_y).unwrap(),
-            ]),
-            (1, 2..) => Some(vec![
-                Rect::new(self.min_x, self.max_x, self.min_y, self.min_y + hy).unwrap(),
-                Rect::new(self.min_x, self.max_x, self.min_y + hy, self.max_y).unwrap(),
-            ]),
-            (2.., 2..) => Some(vec![
-                Rect::new(self.min_x, self.min_x + hx, self.min_y, self.min_y + hy).unwrap(),
-                Rect::new(self.min_x, self.min_x + hx, self.min_y + hy, self.max_y).unwrap(),
-                Rect::new(self.min_x + hx, self.max_x, self.min_y, self.min_y + hy).unwrap(),
-                Rect::new(self.min_x + hx, self.max_x, self.min_y + hy, self.max_y).unwrap(),
-            ]),
-            _ => None,
-        }
-    }
-}
+    fn part_one(sensors: &Self::Parsed<'_>, params: &Params) -> Option<u32> {
+        let line = params.get("row") as i32;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        let covered = coverage(sensors, line);
+        let beacons = sensors
+            .iter()
+            .map(|s| s.nearest_beacon)
+            .filter(|b| b.y == line && covered.contains(b.x as i64))
+            .unique()
+            .count();
 
-    #[test]
-    fn subdivide_1_x_1() {
-        let rect = Rect::new(2, 3, 2, 3).unwrap();
-        let ssubdivisions = rect.subdivisions();
-        assert_eq!(ssubdivisions, None);
+        Some(covered.len() as u32 - beacons as u32)
     }
 
-    #[test]
-    fn subdivide_1_x_2() {
-        let rect = Rect::new(2, 3, 2, 4).unwrap();
-        let ssubdivisions = rect.subdivisions();
-        assert_eq!(
-            ssubdivisions,
-            Some(vec![
-                Rect::new(2, 3, 2, 3).unwrap(),
-                Rect::new(2, 3, 3, 4).unwrap()
-            ])
-        );
-    }
+    fn part_two(sensors: &Self::Parsed<'_>, params: &Params) -> Option<u128> {
+        let range = params.get("bound");
+        let bounds = Interval::new(0, range)?;
 
-    #[test]
-    fn subdivide_2_x_2() {
-        let rect = Rect::new(0, 2, 0, 2).unwrap();
-        assert_eq!(rect.subdivisions().unwrap().len(), 4);
+        (0..=range as i32).find_map(|y| {
+            let gap = coverage(sensors, y).gaps(bounds).next()?;
+            Some(gap.start as u128 * 4_000_000 + y as u128)
+        })
     }
+}
 
-    #[test]
-    fn big_sensor_covers_a_small_area() {
-        let sensor = Sensor::new(Point { x: 10, y: 10 }, Point { x: 0, y: 10 });
-        let rect = Rect::new(8, 13, 8, 13).unwrap();
-        assert!(sensor.covers(rect));
-    }
+/// Checks every position one by one.
+impl Reference for Day15 {
+    fn reference_part_one(sensors: &Self::Parsed<'_>, params: &Params) -> Option<u32> {
+        let y = params.get("row") as i32;
+        let min_x = sensors.iter().map(|s| s.loc.x - s.range as i32).min()?;
+        let max_x = sensors.iter().map(|s| s.loc.x + s.range as i32).max()?;
 
-    #[test]
-    fn rect_with_sides_longer_than_one_return_4_points() {
-        let rect = Rect::new(8, 13, 8, 13).unwrap();
-        let points = vec![
-            Point { x: 8, y: 8 },
-            Point { x: 8, y: 12 },
-            Point { x: 12, y: 8 },
-            Point { x: 12, y: 12 },
-        ];
-        assert!(rect.points().iter().all(|p| points.iter().any(|o| p == o)));
+        let count = (min_x..=max_x)
+            .map(|x| Point { x, y })
+            .filter(|p| sensors.iter().all(|s| s.nearest_beacon != *p))
+            .filter(|p| sensors.iter().any(|s| s.loc.distance(p) <= s.range))
+            .count();
+        Some(count as u32)
     }
 
-    #[test]
-    fn rect_with_a_side_one_return_2_points() {
-        let rect = Rect::new(8, 9, 8, 13).unwrap();
-        let points = vec![Point { x: 8, y: 8 }, Point { x: 8, y: 12 }];
-        assert!(rect.points().iter().all(|p| points.iter().any(|o| p == o)));
-    }
+    fn reference_part_two(sensors: &Self::Parsed<'_>, params: &Params) -> Option<u128> {
+        let bound = params.get("bound") as i32;
 
-    #[test]
-    fn rect_with_other_side_one_return_2_points() {
-        let rect = Rect::new(8, 13, 12, 13).unwrap();
-        let points = vec![Point { x: 8, y: 12 }, Point { x: 12, y: 12 }];
-        assert!(rect.points().iter().all(|p| points.iter().any(|o| p == o)));
+        (0..=bound)
+            .flat_map(|y| (0..=bound).map(move |x| Point { x, y }))
+            .find(|p| sensors.iter().all(|s| s.loc.distance(p) > s.range))
+            .map(|p| p.x as u128 * 4_000_000 + p.y as u128)
     }
+}
 
-    #[test]
-    fn rect_with_one_return_1_points() {
-        let rect = Rect::new(12, 13, 12, 13).unwrap();
-        let points = vec![Point { x: 12, y: 12 }];
-        assert!(rect.points().iter().all(|p| points.iter().any(|o| p == o)));
-    }
+fn main() {
+    let input = &advent_of_code::read_file("inputs", 15);
+    advent_of_code::solve!(Day15, input);
+}
 
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 15);
-        assert_eq!(part_one(&input), Some(26));
-    }
+advent_of_code::aoc_test!(
+    Day15,
+    day = 15,
+    part_one = Some(26),
+    part_two = Some(56000011)
+);
+
+advent_of_code::aoc_differential!(
+    Day15,
+    day = 15,
+    generate = advent_of_code::generate::day15,
+    scale = 1..=8,
+    cases = 200
+);
+
+advent_of_code::aoc_fuzz!(
+    Day15,
+    day = 15,
+    generate = advent_of_code::generate::day15,
+    scale = 1..=8,
+    cases = 50,
+    render = render,
+    mutations = 20,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 15);
-        assert_eq!(part_two(&input), Some(56000011));
+    fn row_coverage_shrinks_with_distance() {
+        let sensor = Sensor::new(Point { x: 8, y: 7 }, Point { x: 2, y: 10 });
+        assert_eq!(sensor.row_coverage(7), Interval::new(-1, 17));
+        assert_eq!(sensor.row_coverage(10), Interval::new(2, 14));
+        assert_eq!(sensor.row_coverage(16), Interval::new(8, 8));
+        assert_eq!(sensor.row_coverage(17), None);
     }
 }
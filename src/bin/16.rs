@@ -1,5 +1,7 @@
-use itertools::Itertools;
-use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use advent_of_code::differential::Reference;
+use advent_of_code::helpers::optimize::{BranchAndBound, Problem};
+use advent_of_code::{Params, Solution};
+use std::collections::{BTreeSet, HashMap, VecDeque};
 
 use nom::{
     branch::alt,
@@ -45,68 +47,71 @@ fn parse_valve(input: &str) -> IResult<&str, ParsedValve> {
     ))
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let valves: Vec<_> = input
-        .lines()
-        .map(|line| parse_valve(line).unwrap().1)
-        .collect();
-
-    let pairs = valves
-        .iter()
-        .map(|v| v.tunnels_to.iter().map(|&n| (v.name, n)))
-        .flatten()
-        .collect::<Vec<(Name, Name)>>();
+/// Only valves with a positive flow rate are worth walking to.
+struct Volcano<'a> {
+    table: DistanceTable<'a>,
+    valves: Vec<(Name<'a>, u32)>,
+}
 
-    let table = DistanceTable::new(pairs);
-    let valved_nodes = valves
-        .iter()
-        .sorted_by(|a, b| a.rate.cmp(&b.rate))
-        .filter_map(|v| if v.rate > 0 { Some(v.name) } else { None })
-        .collect::<Vec<Name>>();
+#[derive(Debug, Clone)]
+struct Tour<'a> {
+    at: Name<'a>,
+    time_left: u32,
+    /// Bitmask over `Volcano::valves`.
+    opened: u64,
+    /// Pressure released until the end by all opened valves.
+    released: u32,
+}
 
-    println!("{table:?}");
+impl<'a> Volcano<'a> {
+    /// Valves still closed, with the time left after walking there and opening them.
+    fn reachable<'t>(&'t self, tour: &'t Tour<'a>) -> impl Iterator<Item = (usize, u32)> + 't {
+        self.valves
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| tour.opened & (1 << i) == 0)
+            .filter_map(|(i, &(name, _))| {
+                let time_left = tour
+                    .time_left
+                    .checked_sub(self.table.distance(tour.at, name))?
+                    .checked_sub(1)?;
+                Some((i, time_left))
+            })
+    }
+}
 
-    let mut current_max = 0;
-    let nodes = BTreeMap::from_iter(valves.iter().map(|v| (v.name, v)));
-    let max = valved_nodes
-        .iter()
-        .copied()
-        .permutations(10)
-        .enumerate()
-        .map(|(i, mut perm)| {
-            if i % 10_000_000 == 0 {
-                println!("{i}");
-            }
-            let mut path = vec!["AA"];
-            perm = perm.iter().rev().copied().collect();
-            path.append(&mut perm);
-            let eval = eval_path(&path[0..], &table, &nodes);
-            if eval > current_max {
-                current_max = eval;
-                println!("New max: {current_max}");
-            }
-            eval
-        })
-        .max()
-        .unwrap();
+impl<'a> Problem for Volcano<'a> {
+    type State = Tour<'a>;
+    type Key = (Name<'a>, u32, u64, u32);
+    type Value = u32;
+
+    fn successors(&self, tour: &Tour<'a>) -> Vec<Tour<'a>> {
+        self.reachable(tour)
+            .map(|(i, time_left)| Tour {
+                at: self.valves[i].0,
+                time_left,
+                opened: tour.opened | 1 << i,
+                released: tour.released + self.valves[i].1 * time_left,
+            })
+            .collect()
+    }
 
-    Some(max)
-}
+    fn value(&self, tour: &Tour<'a>) -> u32 {
+        tour.released
+    }
 
-fn eval_path(path: &[Name], graph: &DistanceTable, nodes: &BTreeMap<Name, &ParsedValve>) -> u32 {
-    let mut pressure = 0;
-    let mut minutes = 0;
-    for (_, pair) in path.windows(2).enumerate() {
-        let dist = graph.distance(pair[0], pair[1]);
-        minutes += dist + 1;
-        minutes = minutes.min(30);
-        pressure += nodes[pair[1]].rate * (30 - minutes);
+    /// As if every closed valve could be reached directly from the current position.
+    fn upper_bound(&self, tour: &Tour<'a>) -> u32 {
+        tour.released
+            + self
+                .reachable(tour)
+                .map(|(i, time_left)| self.valves[i].1 * time_left)
+                .sum::<u32>()
     }
-    pressure
-}
 
-pub fn part_two(input: &str) -> Option<u32> {
-    None
+    fn key(&self, tour: &Tour<'a>) -> Self::Key {
+        (tour.at, tour.time_left, tour.opened, tour.released)
+    }
 }
 
 #[derive(Debug)]
@@ -135,6 +140,7 @@ impl<'a> DistanceTable<'a> {
     }
 
     fn create_nodes_and_distances(&mut self, edges: Vec<(Name<'a>, Name<'a>)>) {
+        let _span = advent_of_code::aoc_span!("create_nodes_and_distances");
         for edge in edges {
             self.nodes.insert(edge.0);
             self.nodes.insert(edge.1);
@@ -149,6 +155,7 @@ impl<'a> DistanceTable<'a> {
                 .or_insert(0);
         }
 
+        let _paths = advent_of_code::aoc_span!("shortest_paths");
         let mut changed = true;
         while changed {
             changed = false;
@@ -193,12 +200,6 @@ fn ordered<'a>(a: Name<'a>, b: Name<'a>) -> [Name<'a>; 2] {
     k
 }
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", 16);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
-}
-
 struct ActionNode<'a> {
     action: Action<'a>,
     children: Vec<ActionNode<'a>>,
@@ -269,8 +270,128 @@ impl<'a> Graph<'a> {
     fn neighbors(&'a self, node: Name<'a>) -> &'a Vec<Name<'a>> {
         &self.node(node).tunnels_to
     }
+
+    /// Steps from `start` to every reachable valve.
+    fn distances_from(&'a self, start: Name<'a>) -> HashMap<Name<'a>, u32> {
+        let mut distances = HashMap::from([(start, 0)]);
+        let mut queue = VecDeque::from([start]);
+        while let Some(valve) = queue.pop_front() {
+            let distance = distances[valve];
+            for &next in self.neighbors(valve) {
+                distances.entry(next).or_insert_with(|| {
+                    queue.push_back(next);
+                    distance + 1
+                });
+            }
+        }
+        distances
+    }
+}
+
+/// Tries every order of opening the `closed` valves, without pruning anything.
+fn most_pressure<'a>(
+    graph: &Graph<'a>,
+    distances: &HashMap<Name<'a>, HashMap<Name<'a>, u32>>,
+    at: Name<'a>,
+    time_left: u32,
+    closed: &[Name<'a>],
+) -> u32 {
+    closed
+        .iter()
+        .enumerate()
+        .filter_map(|(i, &next)| {
+            let time_left = time_left.checked_sub(distances[at].get(next)? + 1)?;
+            let mut rest = closed.to_vec();
+            rest.remove(i);
+            let released = graph.node(next).rate * time_left;
+            Some(released + most_pressure(graph, distances, next, time_left, &rest))
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+struct Day16;
+
+impl Solution for Day16 {
+    type Parsed<'a> = Vec<ParsedValve<'a>>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> advent_of_code::Result<Self::Parsed<'_>> {
+        input.lines().map(|line| Ok(parse_valve(line)?.1)).collect()
+    }
+
+    fn part_one(valves: &Self::Parsed<'_>, _: &Params) -> Option<u32> {
+        let pairs = valves
+            .iter()
+            .flat_map(|v| v.tunnels_to.iter().map(|&n| (v.name, n)))
+            .collect::<Vec<(Name, Name)>>();
+
+        let volcano = Volcano {
+            table: DistanceTable::new(pairs),
+            valves: valves
+                .iter()
+                .filter(|v| v.rate > 0)
+                .map(|v| (v.name, v.rate))
+                .collect(),
+        };
+        let start = Tour {
+            at: "AA",
+            time_left: 30,
+            opened: 0,
+            released: 0,
+        };
+
+        let _span = advent_of_code::aoc_span!("branch_and_bound");
+        Some(BranchAndBound::new().maximize(&volcano, start).value)
+    }
+
+    fn part_two(_valves: &Self::Parsed<'_>, _: &Params) -> Option<u32> {
+        None
+    }
+}
+
+impl Reference for Day16 {
+    fn reference_part_one(valves: &Self::Parsed<'_>, _: &Params) -> Option<u32> {
+        let graph = Graph::new(valves)?;
+        let closed = valves
+            .iter()
+            .filter(|v| v.rate > 0)
+            .map(|v| v.name)
+            .collect::<Vec<_>>();
+        let distances = valves
+            .iter()
+            .map(|v| (v.name, graph.distances_from(v.name)))
+            .collect();
+        Some(most_pressure(&graph, &distances, "AA", 30, &closed))
+    }
+
+    fn reference_part_two(_valves: &Self::Parsed<'_>, _: &Params) -> Option<u32> {
+        None
+    }
+}
+
+fn main() {
+    let input = &advent_of_code::read_file("inputs", 16);
+    advent_of_code::solve!(Day16, input);
 }
 
+advent_of_code::aoc_test!(
+    Day16,
+    day = 16,
+    part_one = Some(1651),
+    #[ignore = "unsolved"]
+    part_two = Some(1707)
+);
+
+advent_of_code::aoc_differential!(
+    Day16,
+    day = 16,
+    generate = advent_of_code::generate::day16,
+    scale = 2..=9,
+    cases = 100
+);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -316,16 +437,4 @@ mod tests {
             ))
         );
     }
-
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 16);
-        assert_eq!(part_one(&input), Some(1651));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 16);
-        assert_eq!(part_two(&input), None);
-    }
 }
@@ -1,7 +1,8 @@
-use std::collections::HashSet;
-
-use itertools::Itertools;
+use advent_of_code::differential::Reference;
+use advent_of_code::helpers::optimize::{BranchAndBound, Problem};
+use advent_of_code::{Param, Params, Solution};
 use nom::{bytes::complete::tag, character::complete, IResult};
+use std::fmt::Display;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Blueprint {
@@ -71,8 +72,8 @@ impl GameState {
     }
 
     fn tick(&self, action: BuildAction) -> Option<GameState> {
-        let mut new_state = self.clone();
-        let clone = new_state.clone();
+        let mut new_state = *self;
+        let clone = new_state;
 
         match action {
             BuildAction::Nothing => {}
@@ -132,34 +133,97 @@ impl GameState {
         self.obsidian -= cost.obsidian;
     }
 
-    fn optimal_geodes(&self) -> u32 {
-        let mut known_states = vec![self.clone()];
-        while known_states[0].time < 24 {
-            println!(
-                "{} {} {:?}",
-                known_states[0].time,
-                known_states.len(),
-                known_states.iter().map(|s| s.geodes).max()
-            );
-            let mut processed_states = Vec::new();
-            for game in known_states.clone().iter() {
-                if game.has_enough(game.blueprint.geode_robot_cost) {
-                    let new = game.tick(BuildAction::Geode).unwrap();
-                    processed_states.push(new);
-                } else {
-                    for action in BuildAction::all() {
-                        if let Some(new) = game.tick(action) {
-                            processed_states.push(new);
-                        }
+    fn optimal_geodes(&self, minutes: u8) -> u32 {
+        let problem = Mining {
+            blueprint: self.blueprint,
+            minutes,
+        };
+        BranchAndBound::new().maximize(&problem, *self).value
+    }
+
+    /// Tries every order of building robots, each as soon as it's affordable, without
+    /// pruning anything.
+    fn most_geodes(&self, minutes: u8) -> u32 {
+        let idle = self.geodes as u32 + self.geode_robots as u32 * (minutes - self.time) as u32;
+        let robots = [
+            BuildAction::Ore,
+            BuildAction::Clay,
+            BuildAction::Obsidian,
+            BuildAction::Geode,
+        ];
+        robots
+            .into_iter()
+            .filter_map(|robot| {
+                let mut state = *self;
+                while state.time < minutes {
+                    match state.tick(robot) {
+                        Some(built) => return Some(built.most_geodes(minutes)),
+                        None => state = state.tick(BuildAction::Nothing)?,
                     }
                 }
+                None
+            })
+            .fold(idle, u32::max)
+    }
+}
+
+struct Mining {
+    blueprint: Blueprint,
+    minutes: u8,
+}
+
+impl Mining {
+    fn remaining(&self, state: &GameState) -> u32 {
+        (self.minutes - state.time) as u32
+    }
+
+    /// More robots of a kind than the most expensive recipe needs can't speed anything up.
+    fn is_useful(&self, state: &GameState, action: BuildAction) -> bool {
+        let costs = [
+            self.blueprint.ore_robot_cost,
+            self.blueprint.clay_robot_cost,
+            self.blueprint.obsidian_robot_cost,
+            self.blueprint.geode_robot_cost,
+        ];
+        match action {
+            BuildAction::Ore => state.ore_robots < costs.iter().map(|c| c.ore).max().unwrap(),
+            BuildAction::Clay => state.clay_robots < self.blueprint.obsidian_robot_cost.clay,
+            BuildAction::Obsidian => {
+                state.obsidian_robots < self.blueprint.geode_robot_cost.obsidian
             }
-            known_states.clear();
-            let set: HashSet<GameState> = HashSet::from_iter(processed_states.iter().copied());
-            let k = set.iter().copied().unique().collect();
-            known_states = k;
+            BuildAction::Nothing | BuildAction::Geode => true,
+        }
+    }
+}
+
+impl Problem for Mining {
+    type State = GameState;
+    type Key = GameState;
+    type Value = u32;
+
+    fn successors(&self, state: &GameState) -> Vec<GameState> {
+        if state.time >= self.minutes {
+            return vec![];
         }
-        known_states.iter().map(|s| s.geodes as u32).max().unwrap()
+        BuildAction::all()
+            .into_iter()
+            .filter(|&action| self.is_useful(state, action))
+            .filter_map(|action| state.tick(action))
+            .collect()
+    }
+
+    fn value(&self, state: &GameState) -> u32 {
+        state.geodes as u32 + state.geode_robots as u32 * self.remaining(state)
+    }
+
+    /// Assumes a new geode robot can be built in every remaining minute.
+    fn upper_bound(&self, state: &GameState) -> u32 {
+        let remaining = self.remaining(state);
+        self.value(state) + remaining * remaining.saturating_sub(1) / 2
+    }
+
+    fn key(&self, state: &GameState) -> GameState {
+        *state
     }
 }
 
@@ -177,7 +241,7 @@ fn blueprint(input: &str) -> IResult<&str, Blueprint> {
     let (input, _) = tag(" clay. Each geode robot costs ")(input)?;
     let (input, geode_robot_ore) = complete::u8(input)?;
     let (input, _) = tag(" ore and ")(input)?;
-    let (input, geode_robot_clay) = complete::u8(input)?;
+    let (input, geode_robot_obsidian) = complete::u8(input)?;
     let (input, _) = tag(" obsidian.")(input)?;
 
     Ok((
@@ -201,37 +265,118 @@ fn blueprint(input: &str) -> IResult<&str, Blueprint> {
             },
             geode_robot_cost: MaterialCost {
                 ore: geode_robot_ore,
-                clay: geode_robot_clay,
-                obsidian: 0,
+                clay: 0,
+                obsidian: geode_robot_obsidian,
             },
         },
     ))
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let blueprints = input.lines().map(|line| blueprint(line).unwrap().1);
-    let games = blueprints.map(GameState::new);
-    let optimal_geodes = games.map(|game| game.optimal_geodes());
-
-    Some(
-        optimal_geodes
-            .inspect(|g| println!("{g:?}"))
-            .enumerate()
-            .map(|(i, g)| (i as u32 + 1) * g)
-            .sum(),
-    )
+impl Display for Blueprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Blueprint {}: Each ore robot costs {} ore. Each clay robot costs {} ore. \
+             Each obsidian robot costs {} ore and {} clay. \
+             Each geode robot costs {} ore and {} obsidian.",
+            self.id,
+            self.ore_robot_cost.ore,
+            self.clay_robot_cost.ore,
+            self.obsidian_robot_cost.ore,
+            self.obsidian_robot_cost.clay,
+            self.geode_robot_cost.ore,
+            self.geode_robot_cost.obsidian,
+        )
+    }
+}
+
+#[cfg(test)]
+fn render(blueprints: &[Blueprint]) -> String {
+    use itertools::Itertools;
+
+    blueprints.iter().join("\n")
+}
+
+struct Day19;
+
+impl Solution for Day19 {
+    type Parsed<'a> = Vec<Blueprint>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    const PARAMS: &'static [Param] = &[Param::new("minutes", 24, 24)];
+
+    fn parse(input: &str) -> advent_of_code::Result<Self::Parsed<'_>> {
+        input.lines().map(|line| Ok(blueprint(line)?.1)).collect()
+    }
+
+    fn part_one(blueprints: &Self::Parsed<'_>, params: &Params) -> Option<u32> {
+        let minutes = params.get("minutes") as u8;
+        let games = blueprints.iter().copied().map(GameState::new);
+        let optimal_geodes = games.map(|game| game.optimal_geodes(minutes));
+
+        Some(
+            optimal_geodes
+                .enumerate()
+                .inspect(|(i, g)| advent_of_code::aoc_debug!("Blueprint {}: {g} geodes", i + 1))
+                .map(|(i, g)| (i as u32 + 1) * g)
+                .sum(),
+        )
+    }
+
+    fn part_two(_blueprints: &Self::Parsed<'_>, _: &Params) -> Option<u32> {
+        None
+    }
 }
 
-pub fn part_two(_input: &str) -> Option<u32> {
-    None
+impl Reference for Day19 {
+    fn reference_part_one(blueprints: &Self::Parsed<'_>, params: &Params) -> Option<u32> {
+        let minutes = params.get("minutes") as u8;
+        let games = blueprints.iter().copied().map(GameState::new);
+        Some(
+            games
+                .map(|game| game.blueprint.id * game.most_geodes(minutes))
+                .sum(),
+        )
+    }
+
+    fn reference_part_two(_blueprints: &Self::Parsed<'_>, _: &Params) -> Option<u32> {
+        None
+    }
 }
 
 fn main() {
     let input = &advent_of_code::read_file("inputs", 19);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(Day19, input);
 }
 
+advent_of_code::aoc_test!(
+    Day19,
+    day = 19,
+    part_one = Some(33),
+    #[ignore = "unsolved"]
+    part_two = Some(3472)
+);
+
+advent_of_code::aoc_differential!(
+    Day19,
+    day = 19,
+    generate = advent_of_code::generate::day19,
+    scale = 1..=3,
+    cases = 20,
+    params = [minutes = 16]
+);
+
+advent_of_code::aoc_fuzz!(
+    Day19,
+    day = 19,
+    generate = advent_of_code::generate::day19,
+    scale = 1..=10,
+    cases = 50,
+    render = render,
+    mutations = 20,
+);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -244,14 +389,9 @@ mod tests {
     }
 
     #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 19);
-        assert_eq!(part_one(&input), Some(33));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 19);
-        assert_eq!(part_two(&input), None);
+    fn building_a_geode_robot_is_not_always_best() {
+        let s = "Blueprint 1: Each ore robot costs 2 ore. Each clay robot costs 1 ore. Each obsidian robot costs 3 ore and 2 clay. Each geode robot costs 3 ore and 1 obsidian.";
+        let (_, blueprint) = blueprint(s).unwrap();
+        assert_eq!(GameState::new(blueprint).optimal_geodes(16), 29);
     }
 }
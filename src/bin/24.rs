@@ -1,4 +1,9 @@
-use nom::{character::complete::one_of, multi::many1, IResult};
+use std::collections::HashSet;
+use std::fmt::Display;
+
+use advent_of_code::helpers::math::lcm;
+use advent_of_code::helpers::viz::{self, ColorMap};
+use advent_of_code::{Params, ParseError, Solution};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 enum Blizzard {
@@ -8,6 +13,19 @@ enum Blizzard {
     Right,
 }
 
+impl Blizzard {
+    fn symbol(&self) -> char {
+        match self {
+            Blizzard::Up => '^',
+            Blizzard::Down => 'v',
+            Blizzard::Left => '<',
+            Blizzard::Right => '>',
+        }
+    }
+}
+
+/// Blizzards inside the walls of the valley, at `(x, y)` from the top left inner corner.
+/// `max_x` and `max_y` are the inner width and height.
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct Map {
     blizzards: Vec<((u32, u32), Blizzard)>,
@@ -15,58 +33,209 @@ struct Map {
     max_y: u32,
 }
 
-fn map(input: &str) -> IResult<&str, Map> {
-    let mut map = Map {
-        blizzards: vec![],
-        max_x: 0,
-        max_y: 0,
-    };
-    let mut iter = input.lines();
-    iter.next();
-    let mut iter = iter.enumerate().peekable();
-    while let Some((y, line)) = iter.next() {
-        if iter.peek().is_some() {}
+impl Map {
+    /// Positions of all blizzards after `minute` minutes, wrapping around at the walls.
+    fn blizzards_at(&self, minute: u32) -> impl Iterator<Item = ((u32, u32), Blizzard)> + '_ {
+        let (w, h) = (self.max_x, self.max_y);
+        let (dx, dy) = (minute % w, minute % h);
+        self.blizzards.iter().map(move |&((x, y), blizzard)| {
+            let pos = match blizzard {
+                Blizzard::Up => (x, (y + h - dy) % h),
+                Blizzard::Down => (x, (y + dy) % h),
+                Blizzard::Left => ((x + w - dx) % w, y),
+                Blizzard::Right => ((x + dx) % w, y),
+            };
+            (pos, blizzard)
+        })
     }
+}
 
-    let (input, _last_line) = many1(one_of("#."))(input)?;
+/// The valley after `minute` minutes, drawn like in the puzzle: a blizzard's arrow or the
+/// number of blizzards sharing a spot.
+struct Valley<'a> {
+    map: &'a Map,
+    minute: u32,
+}
 
-    Ok((input, map))
+impl Display for Valley<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (w, h) = (self.map.max_x as usize, self.map.max_y as usize);
+        let mut cells = vec![vec![None::<(Blizzard, u32)>; w]; h];
+        for ((x, y), blizzard) in self.map.blizzards_at(self.minute) {
+            let cell = &mut cells[y as usize][x as usize];
+            *cell = Some((blizzard, cell.map_or(1, |(_, n)| n + 1)));
+        }
+        writeln!(f, "#.{}", "#".repeat(w))?;
+        for row in cells {
+            let row = row
+                .iter()
+                .map(|cell| match cell {
+                    None => '.',
+                    Some((blizzard, 1)) => blizzard.symbol(),
+                    Some((_, n)) => char::from_digit(*n.min(&9), 10).unwrap(),
+                })
+                .collect::<String>();
+            writeln!(f, "#{row}#")?;
+        }
+        write!(f, "{}.#", "#".repeat(w))
+    }
 }
 
-fn pathfind(map: Map) -> Option<u32> {
-    todo!()
+fn map(input: &str) -> advent_of_code::Result<Map> {
+    let lines = input.lines().collect::<Vec<_>>();
+    if lines.len() < 3 {
+        return Err(ParseError::new("expected a valley surrounded by walls"));
+    }
+    let inner = &lines[1..lines.len() - 1];
+    let mut map = Map {
+        blizzards: vec![],
+        max_x: lines[0].len().saturating_sub(2) as u32,
+        max_y: inner.len() as u32,
+    };
+    for (y, line) in inner.iter().enumerate() {
+        let row = line
+            .strip_prefix('#')
+            .and_then(|line| line.strip_suffix('#'))
+            .filter(|row| row.len() == map.max_x as usize)
+            .ok_or_else(|| ParseError::new(format!("invalid valley row: {line}")))?;
+        for (x, c) in row.chars().enumerate() {
+            let blizzard = match c {
+                '.' => continue,
+                '^' => Blizzard::Up,
+                'v' => Blizzard::Down,
+                '<' => Blizzard::Left,
+                '>' => Blizzard::Right,
+                _ => return Err(ParseError::new(format!("unexpected `{c}` in the valley"))),
+            };
+            map.blizzards.push(((x as u32, y as u32), blizzard));
+        }
+    }
+    if map.max_x == 0 {
+        return Err(ParseError::new("the valley is empty"));
+    }
+    Ok(map)
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (input, map) = map(input).unwrap();
-    assert_eq!(input, "");
-    let i = pathfind(map).unwrap();
-    Some(i)
+/// Records one full cycle of the blizzards, after which they are back in their starting spots.
+fn record_blizzards(map: &Map) {
+    let colors = ColorMap::default()
+        .with('^', [120, 180, 255])
+        .with('v', [120, 180, 255])
+        .with('<', [120, 180, 255])
+        .with('>', [120, 180, 255]);
+    if let Some(mut recorder) = viz::record("24-blizzards", colors) {
+        for minute in 0..lcm(map.max_x, map.max_y) {
+            recorder.push_chars(Valley { map, minute });
+        }
+        recorder.finish();
+    }
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+/// The fewest minutes to cross the valley from the entrance to the exit, dodging blizzards.
+fn pathfind(map: &Map) -> Option<u32> {
+    let (w, h) = (map.max_x as i32, map.max_y as i32);
+    let (entrance, exit) = ((0, -1), (w - 1, h));
+    // every spot at every point of the blizzard cycle is worth reaching at most once
+    let limit = lcm(map.max_x, map.max_y) * (map.max_x * map.max_y + 1);
+    let mut reached = HashSet::from([entrance]);
+    for minute in 1..=limit {
+        let blizzards = map
+            .blizzards_at(minute)
+            .map(|((x, y), _)| (x as i32, y as i32))
+            .collect::<HashSet<_>>();
+        let mut next = HashSet::new();
+        for (x, y) in reached {
+            for (dx, dy) in [(0, 0), (1, 0), (-1, 0), (0, 1), (0, -1)] {
+                let to = (x + dx, y + dy);
+                if to == exit {
+                    return Some(minute);
+                }
+                let inside = (0..w).contains(&to.0) && (0..h).contains(&to.1);
+                if to == entrance || inside && !blizzards.contains(&to) {
+                    next.insert(to);
+                }
+            }
+        }
+        reached = next;
+    }
     None
 }
 
+struct Day24;
+
+impl Solution for Day24 {
+    type Parsed<'a> = Map;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> advent_of_code::Result<Self::Parsed<'_>> {
+        map(input)
+    }
+
+    fn part_one(map: &Self::Parsed<'_>, _: &Params) -> Option<u32> {
+        pathfind(map)
+    }
+
+    fn part_two(_map: &Self::Parsed<'_>, _: &Params) -> Option<u32> {
+        None
+    }
+}
+
 fn main() {
     let input = &advent_of_code::read_file("inputs", 24);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    // recorded before solving, so it isn't part of the timings; `solve!` reports parse errors
+    if let Ok(map) = map(input) {
+        record_blizzards(&map);
+    }
+    advent_of_code::solve!(Day24, input);
 }
 
+advent_of_code::aoc_test!(
+    Day24,
+    day = 24,
+    part_one = Some(18),
+    #[ignore = "unsolved"]
+    part_two = Some(54)
+);
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn test_part_one() {
+    fn draws_the_valley() {
         let input = advent_of_code::read_file("examples", 24);
-        assert_eq!(part_one(&input), None);
+        let map = Day24::parse(&input).unwrap();
+        assert_eq!((map.max_x, map.max_y, map.blizzards.len()), (6, 4, 19));
+        assert_eq!(
+            Valley {
+                map: &map,
+                minute: 0
+            }
+            .to_string(),
+            input.trim_end()
+        );
+        assert_eq!(
+            Valley {
+                map: &map,
+                minute: 1
+            }
+            .to_string(),
+            "#.######\n#.>3.<.#\n#<..<<.#\n#>2.22.#\n#>v..^<#\n######.#"
+        );
+        assert_eq!(
+            Valley {
+                map: &map,
+                minute: 12
+            }
+            .to_string(),
+            input.trim_end()
+        );
     }
 
     #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 24);
-        assert_eq!(part_two(&input), None);
+    fn rejects_broken_walls() {
+        assert!(Day24::parse("#.###\n#>..\n###.#").is_err());
+        assert!(Day24::parse("#.###\n#>x.#\n###.#").is_err());
     }
 }
@@ -3,23 +3,209 @@
  * There is no need to edit this file unless you want to change template functionality.
  */
 use std::io::Write;
-use std::path::PathBuf;
-use std::{env::temp_dir, io, process::Command};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::thread;
+use std::time::Duration;
+use std::{env, env::temp_dir, io, process::Command};
 use std::{fs, process};
 
+use advent_of_code::calendar;
+use advent_of_code::config::Config;
+
 struct Args {
-    day: u8,
+    day: Option<u8>,
     year: Option<i16>,
+    all: bool,
+    force: bool,
 }
 
 fn parse_args() -> Result<Args, pico_args::Error> {
     let mut args = pico_args::Arguments::from_env();
     Ok(Args {
-        day: args.free_from_str()?,
         year: args.opt_value_from_str(["-y", "--year"])?,
+        all: args.contains("--all"),
+        force: args.contains("--force"),
+        day: args.opt_free_from_str()?,
     })
 }
 
+/// What `--all` did for a day.
+#[derive(Debug, PartialEq, Eq)]
+enum Outcome {
+    Downloaded,
+    Skipped,
+    Failed(String),
+}
+
+/// The days of `year` that have unlocked at the unix time `now`.
+fn unlocked_days(year: i64, now: i64) -> Vec<u8> {
+    (1..=25)
+        .filter(|&day| calendar::unlock_time(year, day) <= now)
+        .collect()
+}
+
+/// Whether a day has no input yet, or only the empty one scaffold creates. A copy in the vault
+/// counts as an input, as `read_file` decrypts it.
+fn is_missing(root: &Path, config: &Config, day: u8) -> bool {
+    let input = fs::read_to_string(root.join(config.input_path(day)));
+    input.map_or(true, |input| input.trim().is_empty())
+        && !root.join(config.vault_path(&format!("{day:02}"))).is_file()
+}
+
+/// Fetches every day that is missing, or every day with `force`, and calls `wait` between
+/// two requests.
+fn fetch_all(
+    days: &[u8],
+    force: bool,
+    is_missing: impl Fn(u8) -> bool,
+    mut fetch: impl FnMut(u8) -> Result<(), String>,
+    mut wait: impl FnMut(),
+) -> Vec<(u8, Outcome)> {
+    let mut first = true;
+    days.iter()
+        .map(|&day| {
+            if !force && !is_missing(day) {
+                return (day, Outcome::Skipped);
+            }
+            if !first {
+                wait();
+            }
+            first = false;
+            match fetch(day) {
+                Ok(()) => (day, Outcome::Downloaded),
+                Err(e) => (day, Outcome::Failed(e)),
+            }
+        })
+        .collect()
+}
+
+fn summary(outcomes: &[(u8, Outcome)]) -> String {
+    let count = |outcome: &Outcome| outcomes.iter().filter(|(_, o)| o == outcome).count();
+    let failed = outcomes
+        .iter()
+        .filter_map(|(day, outcome)| match outcome {
+            Outcome::Failed(e) => Some(format!("\n  day {day:02}: {e}")),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let mut summary = format!(
+        "Downloaded {}, skipped {} that exist, {} failed",
+        count(&Outcome::Downloaded),
+        count(&Outcome::Skipped),
+        failed.len()
+    );
+    if !failed.is_empty() {
+        summary.push(':');
+        summary.extend(failed);
+    }
+    summary
+}
+
+/// The session cookie, from `ADVENT_OF_CODE_SESSION` or the session file aoc-cli reads.
+fn session_cookie(config: &Config) -> Result<String, String> {
+    if let Ok(session) = env::var("ADVENT_OF_CODE_SESSION") {
+        return Ok(session.trim().to_string());
+    }
+    let path = config
+        .session_file()
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".adventofcode.session")))
+        .ok_or("could not find the session cookie, set ADVENT_OF_CODE_SESSION")?;
+    fs::read_to_string(&path)
+        .map(|session| session.trim().to_string())
+        .map_err(|e| format!("could not read session cookie \"{}\": {e}", path.display()))
+}
+
+/// Downloads an input with curl, which sends our User-Agent. The cookie goes through stdin
+/// so it doesn't show up in the process list.
+fn fetch_input(
+    user_agent: &str,
+    session: &str,
+    year: i64,
+    day: u8,
+    path: &Path,
+) -> Result<(), String> {
+    let url = format!("https://adventofcode.com/{year}/day/{day}/input");
+    let download = path.with_extension("download");
+    let mut curl = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--header", "@-"])
+        .arg("--user-agent")
+        .arg(user_agent)
+        .arg("--output")
+        .arg(&download)
+        .arg(&url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("failed to spawn curl: {e}"))?;
+    if let Some(mut stdin) = curl.stdin.take() {
+        writeln!(stdin, "Cookie: session={session}")
+            .map_err(|e| format!("could not pass the cookie to curl: {e}"))?;
+    }
+    let output = curl
+        .wait_with_output()
+        .map_err(|e| format!("curl failed: {e}"))?;
+    if !output.status.success() {
+        remove_file(&download);
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    fs::rename(&download, path).map_err(|e| format!("could not write input: {e}"))
+}
+
+fn download_all(args: &Args, config: &Config) -> i32 {
+    let now = calendar::now();
+    let year = match args.year.or(config.year) {
+        Some(year) => i64::from(year),
+        None => calendar::latest_event(now),
+    };
+    let days = unlocked_days(year, now);
+    if days.is_empty() {
+        println!("🎄 No puzzle of {year} has unlocked yet.");
+        return 0;
+    }
+    let Some(user_agent) = &config.user_agent else {
+        eprintln!(
+            "Set `user_agent` in the `[download]` table of aoc.toml to a way to contact you, \
+             e.g. \"github.com/you/advent-of-code by you@example.com\", before using `--all`."
+        );
+        return 1;
+    };
+    let session = match session_cookie(config) {
+        Ok(session) => session,
+        Err(e) => {
+            eprintln!("{e}");
+            return 1;
+        }
+    };
+    if let Err(e) = fs::create_dir_all(&config.inputs) {
+        eprintln!("could not create input directory: {e}");
+        return 1;
+    }
+
+    println!("Downloading inputs of {year}, {}s apart", config.delay);
+    let outcomes = fetch_all(
+        &days,
+        args.force,
+        |day| is_missing(Path::new(""), config, day),
+        |day| {
+            println!("Downloading day {day:02}...");
+            fetch_input(user_agent, &session, year, day, &config.input_path(day))
+        },
+        || thread::sleep(Duration::from_secs(config.delay)),
+    );
+
+    println!("---");
+    println!("🎄 {}", summary(&outcomes));
+    match outcomes
+        .iter()
+        .any(|(_, o)| matches!(o, Outcome::Failed(_)))
+    {
+        true => 1,
+        false => 0,
+    }
+}
+
 fn remove_file(path: &PathBuf) {
     #[allow(unused_must_use)]
     {
@@ -47,8 +233,27 @@ fn main() {
         }
     };
 
-    let day_padded = format!("{:02}", args.day);
-    let input_path = format!("src/inputs/{day_padded}.txt");
+    let config = match Config::load(Path::new(".")) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Failed to read configuration: {e}");
+            exit_with_status(1, &tmp_file_path);
+        }
+    };
+
+    if args.all {
+        if let Some(day) = args.day {
+            eprintln!("`--all` downloads every day, leave out the day ({day}) or `--all`.");
+            exit_with_status(1, &tmp_file_path);
+        }
+        exit_with_status(download_all(&args, &config), &tmp_file_path);
+    }
+
+    let Some(day) = args.day else {
+        eprintln!("Need to specify a day or `--all`. example: `cargo download 7`");
+        exit_with_status(1, &tmp_file_path);
+    };
+    let input_path = config.input_path(day);
 
     // check if aoc binary exists and is callable.
     if Command::new("aoc").arg("-V").output().is_err() {
@@ -58,16 +263,21 @@ fn main() {
 
     let mut cmd_args = vec![];
 
-    if let Some(year) = args.year {
+    if let Some(year) = args.year.or(config.year) {
         cmd_args.push("--year".into());
         cmd_args.push(year.to_string());
     }
 
+    if let Some(session_file) = config.session_file() {
+        cmd_args.push("--session-file".into());
+        cmd_args.push(session_file.to_string_lossy().to_string());
+    }
+
     cmd_args.append(&mut vec![
         "--input-file".into(),
         tmp_file_path.to_string_lossy().to_string(),
         "--day".into(),
-        args.day.to_string(),
+        day.to_string(),
         "download".into(),
     ]);
 
@@ -94,7 +304,10 @@ fn main() {
     match fs::copy(&tmp_file_path, &input_path) {
         Ok(_) => {
             println!("---");
-            println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+            println!(
+                "🎄 Successfully wrote input to \"{}\".",
+                input_path.display()
+            );
             exit_with_status(0, &tmp_file_path);
         }
         Err(e) => {
@@ -103,3 +316,74 @@ fn main() {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_unlocked_days() {
+        let day_three = calendar::unlock_time(2022, 3);
+        assert_eq!(unlocked_days(2022, day_three - 1), [1, 2]);
+        assert_eq!(unlocked_days(2022, day_three), [1, 2, 3]);
+        assert_eq!(unlocked_days(2021, day_three).len(), 25);
+        assert!(unlocked_days(2023, day_three).is_empty());
+    }
+
+    #[test]
+    fn finds_missing_inputs() {
+        let root = std::env::temp_dir().join(format!("download-{}", process::id()));
+        let config = Config::default();
+        for dir in [&config.inputs, &config.vault] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        fs::write(root.join(config.input_path(1)), "1\n").unwrap();
+        fs::write(root.join(config.input_path(2)), "\n").unwrap();
+        fs::write(root.join(config.vault_path("03")), "").unwrap();
+        let missing = (1..=4)
+            .filter(|&day| is_missing(&root, &config, day))
+            .collect::<Vec<_>>();
+        assert_eq!(missing, [2, 4]);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn fetches_missing_days_apart() {
+        let mut fetched = Vec::new();
+        let mut waits = 0;
+        let outcomes = fetch_all(
+            &[1, 2, 3, 4],
+            false,
+            |day| day != 2,
+            |day| {
+                fetched.push(day);
+                match day {
+                    4 => Err("404 Not Found".to_string()),
+                    _ => Ok(()),
+                }
+            },
+            || waits += 1,
+        );
+        assert_eq!(fetched, [1, 3, 4]);
+        assert_eq!(waits, 2);
+        assert_eq!(
+            outcomes,
+            [
+                (1, Outcome::Downloaded),
+                (2, Outcome::Skipped),
+                (3, Outcome::Downloaded),
+                (4, Outcome::Failed("404 Not Found".to_string())),
+            ]
+        );
+        assert_eq!(
+            summary(&outcomes),
+            "Downloaded 2, skipped 1 that exist, 1 failed:\n  day 04: 404 Not Found"
+        );
+
+        let outcomes = fetch_all(&[1, 2], true, |_| false, |_| Ok(()), || {});
+        assert_eq!(
+            summary(&outcomes),
+            "Downloaded 2, skipped 0 that exist, 0 failed"
+        );
+    }
+}
@@ -0,0 +1,56 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::process;
+
+use advent_of_code::generate::{self, Rng};
+
+struct Args {
+    day: u8,
+    scale: Option<usize>,
+    seed: u64,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        day: args.free_from_str()?,
+        scale: args.opt_value_from_str(["-s", "--scale"])?,
+        seed: args.opt_value_from_str("--seed")?.unwrap_or(0),
+    })
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {e}");
+            eprintln!("usage: `cargo generate <day> [--scale <n>] [--seed <n>]`");
+            process::exit(1);
+        }
+    };
+
+    let Some(generator) = generate::generator(args.day) else {
+        let days = generate::GENERATORS
+            .iter()
+            .map(|g| format!("{:02}", g.day))
+            .collect::<Vec<_>>();
+        eprintln!(
+            "No generator for day {:02}, available: {}",
+            args.day,
+            days.join(", ")
+        );
+        process::exit(1);
+    };
+
+    let scale = args.scale.unwrap_or(generator.real_scale);
+    eprintln!(
+        "Generating day {:02} with {scale} {} (seed {})",
+        args.day, generator.unit, args.seed
+    );
+    println!(
+        "{}",
+        (generator.generate)(&mut Rng::new(args.seed), scale).trim_end()
+    );
+}
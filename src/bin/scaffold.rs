@@ -3,112 +3,575 @@
  * There is no need to edit this file unless you want to change template functionality.
  */
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
     process,
 };
 
-const MODULE_TEMPLATE: &str = r###"pub fn part_one(input: &str) -> Option<u32> {
-    None
+use advent_of_code::calendar;
+use advent_of_code::config::Config;
+use advent_of_code::registry;
+
+/// Used when the configured template directory does not exist.
+const MODULE_TEMPLATE: &str = include_str!("../../templates/default.rs");
+
+const DEFAULT_TEMPLATE: &str = "default";
+
+struct Args {
+    days: Vec<u8>,
+    template: Option<String>,
+    title: Option<String>,
+    year: Option<i16>,
+    dry_run: bool,
+    repair: bool,
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    None
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        template: args.opt_value_from_str(["-t", "--template"])?,
+        title: args.opt_value_from_str("--title")?,
+        year: args.opt_value_from_str(["-y", "--year"])?,
+        dry_run: args.contains("--dry-run"),
+        repair: args.contains("--repair"),
+        days: args.free_from_fn(registry::parse_days)?,
+    })
 }
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", DAY);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+/// Reads the template called `name` from a directory of templates, or the single template
+/// at `path`.
+fn load_template(path: &Path, name: Option<&str>) -> Result<String, String> {
+    if path.is_dir() {
+        let name = name.unwrap_or(DEFAULT_TEMPLATE);
+        let file = path.join(format!("{name}.rs"));
+        return fs::read_to_string(&file).map_err(|e| {
+            let mut available = fs::read_dir(path)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .filter_map(|entry| {
+                    let name = entry.file_name().into_string().ok()?;
+                    name.strip_suffix(".rs").map(str::to_string)
+                })
+                .collect::<Vec<_>>();
+            available.sort();
+            format!(
+                "could not read template \"{}\": {e} (available: {})",
+                file.display(),
+                available.join(", ")
+            )
+        });
+    }
+    match name {
+        Some(name) if name != DEFAULT_TEMPLATE && path.is_file() => Err(format!(
+            "\"{}\" is a single template, there is no template called `{name}`",
+            path.display()
+        )),
+        Some(name) if name != DEFAULT_TEMPLATE => Err(format!(
+            "there is no template directory at \"{}\" to find `{name}` in",
+            path.display()
+        )),
+        _ if path.is_file() => fs::read_to_string(path)
+            .map_err(|e| format!("could not read template \"{}\": {e}", path.display())),
+        _ => Ok(MODULE_TEMPLATE.to_string()),
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Replaces every `{{name}}` in `template` by its value, where `name` is an identifier.
+/// Other braces are copied, such as `format!("{{}}")`, and `\{{` is a literal `{{`, for
+/// `println!("\{{x}}")`. Unknown names and unclosed placeholders are errors, so a typo
+/// doesn't end up in the scaffolded file.
+fn render(template: &str, values: &[(&str, &str)]) -> Result<String, String> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        if rest[..start].ends_with('\\') {
+            out.push_str(&rest[..start - 1]);
+            out.push_str("{{");
+            rest = &rest[start + 2..];
+            continue;
+        }
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let inner = after.trim_start_matches(' ');
+        let name_len = inner
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(inner.len());
+        let (name, tail) = inner.split_at(name_len);
+        let tail = tail.trim_start_matches(' ');
+        let is_name = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_');
+        if !is_name || !tail.starts_with("}}") {
+            if is_name && (tail.is_empty() || tail.starts_with(['\n', '\r'])) {
+                return Err(format!("unclosed placeholder `{}`", line_of(after)));
+            }
+            out.push_str("{{");
+            rest = after;
+            continue;
+        }
+        let value = values
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value)
+            .ok_or_else(|| format!("unknown placeholder `{{{{{name}}}}}`"))?;
+        out.push_str(value);
+        rest = &tail[2..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
 
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", DAY);
-        assert_eq!(part_one(&input), None);
+fn line_of(text: &str) -> &str {
+    text.lines().next().unwrap_or_default()
+}
+
+/// The title from a downloaded description, whose heading reads `--- Day 7: Title ---`,
+/// with the dashes escaped in some versions of aoc-cli.
+fn parse_title(description: &str) -> Option<String> {
+    description.lines().find_map(|line| {
+        let heading = line
+            .trim_start_matches(['#', ' ', '\\'])
+            .strip_prefix("--- Day ")?;
+        let (_, title) = heading.split_once(": ")?;
+        Some(title.trim_end_matches([' ', '-', '\\']).to_string())
+    })
+}
+
+/// A file scaffold creates, or updates if it has `original` contents.
+#[derive(Debug, PartialEq, Eq)]
+struct Planned {
+    /// What the file is for, as in "Created empty input file".
+    kind: &'static str,
+    path: PathBuf,
+    contents: String,
+    original: Option<String>,
+}
+
+impl Planned {
+    /// What is done to the file, as `("create", "Created")`.
+    fn verb(&self) -> (&'static str, &'static str) {
+        match self.original {
+            Some(_) => ("update", "Updated"),
+            None => ("create", "Created"),
+        }
     }
+}
 
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", DAY);
-        assert_eq!(part_two(&input), None);
+/// The files to create for a day. Inputs and examples that exist are kept, and so is the
+/// module when repairing; without `repair` an existing module is an error.
+fn plan_day(
+    config: &Config,
+    day: u8,
+    module: String,
+    repair: bool,
+) -> Result<Vec<Planned>, String> {
+    let module_path = config.solution_path(day);
+    if module_path.exists() && !repair {
+        return Err(format!(
+            "\"{}\" already exists, use `--repair` to only add the missing files",
+            module_path.display()
+        ));
     }
+    let files = [
+        ("module file", module_path, module),
+        ("empty input file", config.input_path(day), String::new()),
+        (
+            "empty example file",
+            config.example_path(day),
+            String::new(),
+        ),
+    ];
+    Ok(files
+        .into_iter()
+        .filter(|(_, path, _)| !path.exists())
+        .map(|(kind, path, contents)| Planned {
+            kind,
+            path,
+            contents,
+            original: None,
+        })
+        .collect())
 }
-"###;
 
-fn parse_args() -> Result<u8, pico_args::Error> {
-    let mut args = pico_args::Arguments::from_env();
-    args.free_from_str()
+/// Updates to the files that register `days`, see [`registry::edits`].
+fn plan_registration(days: &[u8]) -> Result<Vec<Planned>, String> {
+    Ok(registry::edits(Path::new(""), days, true)?
+        .into_iter()
+        .map(|edit| Planned {
+            kind: edit.kind,
+            path: edit.path,
+            contents: edit.contents,
+            original: Some(edit.original),
+        })
+        .collect())
 }
 
-fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create_new(true).open(path)
+/// Creates or updates every planned file, with any missing directories. If one of them
+/// fails, undoes everything done so far and returns why.
+fn apply(plan: &[Planned]) -> Result<(), String> {
+    let mut done = Vec::new();
+    for file in plan {
+        if let Err(e) = create(file, &mut done) {
+            for (path, original) in done.iter().rev() {
+                #[allow(unused_must_use)]
+                {
+                    match original {
+                        Some(original) => fs::write(path, original),
+                        None if path.is_dir() => fs::remove_dir(path),
+                        None => fs::remove_file(path),
+                    };
+                }
+            }
+            return Err(format!(
+                "Failed to {} {} \"{}\": {e}",
+                file.verb().0,
+                file.kind,
+                file.path.display()
+            ));
+        }
+    }
+    Ok(())
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+/// Writes a planned file, remembering every path it creates or updates with the contents
+/// to restore.
+fn create(file: &Planned, done: &mut Vec<(PathBuf, Option<String>)>) -> io::Result<()> {
+    if let Some(original) = &file.original {
+        done.push((file.path.clone(), Some(original.clone())));
+        return fs::write(&file.path, &file.contents);
+    }
+    if let Some(parent) = file.path.parent() {
+        let missing = parent
+            .ancestors()
+            .take_while(|dir| !dir.as_os_str().is_empty() && !dir.exists())
+            .collect::<Vec<_>>();
+        for dir in missing.into_iter().rev() {
+            fs::create_dir(dir)?;
+            done.push((dir.to_path_buf(), None));
+        }
+    }
+    let mut f = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&file.path)?;
+    done.push((file.path.clone(), None));
+    f.write_all(file.contents.as_bytes())
 }
 
 fn main() {
-    let day = match parse_args() {
-        Ok(day) => day,
-        Err(_) => {
-            eprintln!("Need to specify a day (as integer). example: `cargo scaffold 7`");
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {e}");
+            eprintln!("Need to specify a day (as integer) or days. example: `cargo scaffold 7`, `cargo scaffold 1-25`");
+            eprintln!("usage: `cargo scaffold <days> [--template <name>] [--title <title>] [--year <year>] [--dry-run] [--repair]`");
             process::exit(1);
         }
     };
 
-    let day_padded = format!("{day:02}");
+    if args.days.len() > 1 && args.title.is_some() {
+        eprintln!("`--title` can only be used when scaffolding a single day.");
+        process::exit(1);
+    }
 
-    let input_path = format!("src/inputs/{day_padded}.txt");
-    let example_path = format!("src/examples/{day_padded}.txt");
-    let module_path = format!("src/bin/{day_padded}.rs");
+    let config = match Config::load(Path::new(".")) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Failed to read configuration: {e}");
+            process::exit(1);
+        }
+    };
 
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
+    let template = match load_template(&config.template, args.template.as_deref()) {
+        Ok(template) => template,
         Err(e) => {
-            eprintln!("Failed to create module file: {e}");
+            eprintln!("Failed to load template: {e}");
             process::exit(1);
         }
     };
 
-    match file.write_all(MODULE_TEMPLATE.replace("DAY", &day.to_string()).as_bytes()) {
-        Ok(_) => {
-            println!("Created module file \"{}\"", &module_path);
+    let year = match args.year.or(config.year) {
+        Some(year) => i64::from(year),
+        None => calendar::latest_event(calendar::now()),
+    };
+
+    // plan every day before creating anything, so a conflict leaves the tree untouched
+    let mut plan = Vec::new();
+    for &day in &args.days {
+        let day_padded = format!("{day:02}");
+        let title = args
+            .title
+            .clone()
+            .or_else(|| {
+                let description =
+                    fs::read_to_string(format!("src/puzzles/{day_padded}.md")).ok()?;
+                parse_title(&description)
+            })
+            .unwrap_or_else(|| format!("Day {day}"));
+
+        let module = render(
+            &template,
+            &[
+                ("day", &day.to_string()),
+                ("day_padded", &day_padded),
+                ("year", &year.to_string()),
+                ("title", &title),
+                ("answer_type", &config.answer_type),
+            ],
+        )
+        .map_err(|e| format!("Failed to fill in template: {e}"));
+
+        match module.and_then(|module| plan_day(&config, day, module, args.repair)) {
+            Ok(files) => plan.extend(files),
+            Err(e) => {
+                eprintln!("Day {day_padded}: {e}");
+                process::exit(1);
+            }
         }
+    }
+
+    match plan_registration(&args.days) {
+        Ok(files) => plan.extend(files),
         Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
+            eprintln!("{e}");
             process::exit(1);
         }
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+    if plan.is_empty() {
+        println!("🎄 Nothing to do, every file exists and every day is registered.");
+        return;
+    }
+
+    if args.dry_run {
+        for file in &plan {
+            if file.contents.is_empty() || file.original.is_some() {
+                let verb = file.verb().0;
+                println!("Would {verb} {} \"{}\"", file.kind, file.path.display());
+            } else {
+                println!("Would create {} \"{}\":", file.kind, file.path.display());
+                println!("---");
+                print!("{}", file.contents);
+                println!("---");
+            }
         }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+        return;
+    }
+
+    if let Err(e) = apply(&plan) {
+        eprintln!("{e}");
+        eprintln!("Rolled back, no files were created or changed.");
+        process::exit(1);
+    }
+    for file in &plan {
+        println!(
+            "{} {} \"{}\"",
+            file.verb().1,
+            file.kind,
+            file.path.display()
+        );
+    }
+
+    println!("---");
+    match args.days.as_slice() {
+        [day] => println!("🎄 Type `cargo solve {day:02}` to run your solution."),
+        days => println!(
+            "🎄 Scaffolded {} days, type `cargo solve <day>` to run a solution.",
+            days.len()
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_placeholders() {
+        let values = [("day", "7"), ("day_padded", "07")];
+        assert_eq!(
+            render("struct Day{{day_padded}}; // DAY {{ day }}", &values),
+            Ok("struct Day07; // DAY 7".to_string())
+        );
+        assert_eq!(
+            render("{{dya}}", &values),
+            Err("unknown placeholder `{{dya}}`".to_string())
+        );
+        assert_eq!(
+            render("a {{day\nb", &values),
+            Err("unclosed placeholder `day`".to_string())
+        );
+        assert_eq!(
+            render(r#"println!("{{}} {{{}}} {{day:?}}", {{day}});"#, &values),
+            Ok(r#"println!("{{}} {{{}}} {{day:?}}", 7);"#.to_string())
+        );
+        assert_eq!(
+            render(r#"format!("\{{day}}")"#, &values),
+            Ok(r#"format!("{{day}}")"#.to_string())
+        );
+    }
+
+    #[test]
+    fn renders_every_template() {
+        let values = [
+            ("day", "7"),
+            ("day_padded", "07"),
+            ("year", "2022"),
+            ("title", "No Space Left On Device"),
+            ("answer_type", "u64"),
+        ];
+        for name in ["default", "grid", "nom"] {
+            let template = load_template(Path::new("templates"), Some(name)).unwrap();
+            let module = render(&template, &values).unwrap();
+            assert!(module.contains("struct Day07;"), "{name}");
+            assert!(module.contains("type PartOne = u64;"), "{name}");
+            assert!(
+                module.contains("/// No Space Left On Device, https://adventofcode.com/2022/day/7"),
+                "{name}"
+            );
         }
+        assert_eq!(
+            load_template(Path::new("missing"), None),
+            Ok(MODULE_TEMPLATE.to_string())
+        );
+        assert!(load_template(Path::new("templates"), Some("hex"))
+            .unwrap_err()
+            .ends_with("(available: default, grid, nom)"));
+        assert!(load_template(Path::new("templates/grid.rs"), Some("nom")).is_err());
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    #[test]
+    fn finds_title() {
+        for heading in [
+            "## --- Day 7: No Space Left On Device ---",
+            "## \\--- Day 7: No Space Left On Device \\---",
+        ] {
+            let description = format!("{heading}\n\nThe device...");
+            assert_eq!(
+                parse_title(&description),
+                Some("No Space Left On Device".to_string())
+            );
         }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+        assert_eq!(parse_title("No heading"), None);
+    }
+
+    fn temp_config(name: &str) -> Config {
+        let dir = std::env::temp_dir().join(format!("scaffold-{name}-{}", process::id()));
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir).unwrap();
+        Config {
+            solutions: dir.join("bin"),
+            inputs: dir.join("inputs"),
+            examples: dir.join("examples"),
+            answers: dir.join("answers"),
+            ..Config::default()
         }
     }
 
-    println!("---");
-    println!(
-        "🎄 Type `cargo solve {}` to run your solution.",
-        &day_padded
-    );
+    #[test]
+    fn scaffolds_and_repairs() {
+        let config = temp_config("repair");
+        let plan = plan_day(&config, 7, "mod".to_string(), false).unwrap();
+        assert_eq!(plan.len(), 3);
+        apply(&plan).unwrap();
+        assert_eq!(fs::read_to_string(config.solution_path(7)).unwrap(), "mod");
+        assert_eq!(fs::read_to_string(config.input_path(7)).unwrap(), "");
+
+        assert!(plan_day(&config, 7, "mod".to_string(), false)
+            .unwrap_err()
+            .contains("use `--repair`"));
+        assert!(plan_day(&config, 7, "mod".to_string(), true)
+            .unwrap()
+            .is_empty());
+
+        fs::remove_file(config.example_path(7)).unwrap();
+        let plan = plan_day(&config, 7, "changed".to_string(), true).unwrap();
+        assert_eq!(
+            plan,
+            [Planned {
+                kind: "empty example file",
+                path: config.example_path(7),
+                contents: String::new(),
+                original: None,
+            }]
+        );
+        apply(&plan).unwrap();
+        assert!(config.example_path(7).exists());
+        assert_eq!(fs::read_to_string(config.solution_path(7)).unwrap(), "mod");
+        fs::remove_dir_all(config.solutions.parent().unwrap()).unwrap();
+    }
+
+    /// Scaffolds a day from every template into a scratch crate and runs its example tests,
+    /// which read the empty example file a new day starts with.
+    #[test]
+    fn scaffolded_days_pass_their_tests() {
+        let manifest = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let config = temp_config("templates");
+        let root = config.solutions.parent().unwrap().to_path_buf();
+        let config = Config {
+            solutions: root.join("src/bin"),
+            inputs: root.join("src/inputs"),
+            examples: root.join("src/examples"),
+            answers: root.join("src/answers"),
+            ..config
+        };
+        fs::write(
+            root.join("Cargo.toml"),
+            format!(
+                "[package]\nname = \"scaffold-check\"\nversion = \"0.0.0\"\nedition = \"2021\"\n\n\
+                 [dependencies]\nadvent_of_code = {{ path = {:?} }}\nnom = \"7.1.1\"\n\n[workspace]\n",
+                manifest.display()
+            ),
+        )
+        .unwrap();
+        // resolve to the versions already built for this crate
+        fs::copy(manifest.join("Cargo.lock"), root.join("Cargo.lock")).ok();
+
+        for (day, name) in (20..).zip(["default", "grid", "nom"]) {
+            let template = load_template(&manifest.join("templates"), Some(name)).unwrap();
+            let day_padded = format!("{day:02}");
+            let module = render(
+                &template,
+                &[
+                    ("day", &day.to_string()),
+                    ("day_padded", &day_padded),
+                    ("year", "2022"),
+                    ("title", name),
+                    ("answer_type", "u32"),
+                ],
+            )
+            .unwrap();
+            apply(&plan_day(&config, day, module, false).unwrap()).unwrap();
+        }
+
+        let output = process::Command::new(std::env::var("CARGO").unwrap_or("cargo".into()))
+            .args(["test", "--offline", "--bins", "--quiet"])
+            .env("CARGO_TARGET_DIR", manifest.join("target/scaffold-check"))
+            .current_dir(&root)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}\n{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn rolls_back_on_failure() {
+        let mut config = temp_config("rollback");
+        let root = config.solutions.parent().unwrap().to_path_buf();
+        // a file where the examples directory should be
+        fs::write(root.join("blocked"), "").unwrap();
+        config.examples = root.join("blocked").join("examples");
+
+        let plan = plan_day(&config, 7, "mod".to_string(), false).unwrap();
+        let error = apply(&plan).unwrap_err();
+        assert!(error.starts_with("Failed to create empty example file"));
+        assert!(!config.solutions.exists());
+        assert!(!config.inputs.exists());
+        fs::remove_dir_all(root).unwrap();
+    }
 }
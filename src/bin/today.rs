@@ -0,0 +1,342 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::{self, Command};
+use std::thread;
+use std::time::Duration;
+
+use advent_of_code::calendar::{self, unlock_time, SECONDS_PER_DAY};
+use advent_of_code::config::Config;
+
+/// Tries to fetch a puzzle before giving up; right after unlock the site is often slow.
+const FETCH_ATTEMPTS: u32 = 6;
+
+struct Args {
+    early: i64,
+    watch: bool,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        early: args.opt_value_from_str("--early")?.unwrap_or(60),
+        watch: !args.contains("--no-watch"),
+    })
+}
+
+/// The time and waiting, so the countdown and backoff run without a real clock in tests.
+trait Clock {
+    /// Seconds since the unix epoch.
+    fn now(&self) -> i64;
+    fn sleep(&self, duration: Duration);
+}
+
+struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> i64 {
+        calendar::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Puzzle {
+    /// The puzzle to work on, unlocked at `unlock`, which may still lie ahead.
+    Day { year: i64, day: u8, unlock: i64 },
+    /// No puzzle unlocks within the next `early` seconds and none is out today.
+    OutOfSeason { next: i64 },
+}
+
+/// The puzzle to work on at `now`: the next one if it unlocks within `early` seconds,
+/// otherwise the one that unlocked today.
+fn puzzle_at(now: i64, early: i64) -> Puzzle {
+    let (year, month, day) = calendar::local_date(now);
+    let today = (month == 12 && day <= 25).then_some((year, day as u8));
+
+    let next = match today {
+        Some((year, 25)) => (year + 1, 1),
+        Some((year, day)) => (year, day + 1),
+        None if month == 12 => (year + 1, 1),
+        None => (year, 1),
+    };
+    let next_unlock = unlock_time(next.0, next.1);
+
+    if next_unlock - now <= early {
+        return Puzzle::Day {
+            year: next.0,
+            day: next.1,
+            unlock: next_unlock,
+        };
+    }
+    match today {
+        Some((year, day)) => Puzzle::Day {
+            year,
+            day,
+            unlock: unlock_time(year, day),
+        },
+        None => Puzzle::OutOfSeason { next: next_unlock },
+    }
+}
+
+fn format_duration(seconds: i64) -> String {
+    let (days, rest) = (seconds / SECONDS_PER_DAY, seconds % SECONDS_PER_DAY);
+    let clock = format!("{:02}:{:02}:{:02}", rest / 3600, rest / 60 % 60, rest % 60);
+    match days {
+        0 => clock,
+        1 => format!("1 day {clock}"),
+        n => format!("{n} days {clock}"),
+    }
+}
+
+/// Counts down on a single line until `unlock`.
+fn wait_for_unlock(clock: &impl Clock, day: u8, unlock: i64, out: &mut impl Write) {
+    let mut waited = false;
+    loop {
+        let left = unlock - clock.now();
+        if left <= 0 {
+            break;
+        }
+        write!(
+            out,
+            "\r⏳ Day {day:02} unlocks in {}",
+            format_duration(left)
+        )
+        .ok();
+        out.flush().ok();
+        waited = true;
+        clock.sleep(Duration::from_secs(1));
+    }
+    if waited {
+        writeln!(out, "\r🔓 Day {day:02} is unlocked!{:20}", "").ok();
+    }
+}
+
+/// Calls `fetch` until it succeeds, doubling the wait after every failure.
+fn with_backoff(
+    clock: &impl Clock,
+    attempts: u32,
+    mut fetch: impl FnMut() -> Result<(), String>,
+) -> Result<(), String> {
+    let mut delay = Duration::from_secs(1);
+    let mut attempt = 1;
+    loop {
+        match fetch() {
+            Ok(()) => return Ok(()),
+            Err(e) if attempt == attempts => return Err(e),
+            Err(e) => {
+                eprintln!("Attempt {attempt} failed, retrying in {delay:?}: {e}");
+                clock.sleep(delay);
+                delay *= 2;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+fn cargo(args: &[&str]) -> bool {
+    Command::new("cargo")
+        .args(args)
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Downloads the input and the description with aoc-cli.
+fn fetch(
+    config: &Config,
+    year: i64,
+    day: u8,
+    input_path: &Path,
+    puzzle_path: &Path,
+) -> Result<(), String> {
+    let mut aoc = Command::new("aoc");
+    if let Some(session_file) = config.session_file() {
+        aoc.arg("--session-file").arg(session_file);
+    }
+    let output = aoc
+        .args(["--year", &year.to_string(), "--day", &day.to_string()])
+        .arg("--input-file")
+        .arg(input_path)
+        .arg("--puzzle-file")
+        .arg(puzzle_path)
+        .args(["--overwrite", "download"])
+        .output()
+        .map_err(|e| format!("failed to spawn aoc-cli: {e}"))?;
+    match output.status.success() {
+        true => Ok(()),
+        false => Err(String::from_utf8_lossy(&output.stderr).trim().to_string()),
+    }
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {e}");
+            eprintln!("usage: `cargo today [--early <seconds>] [--no-watch]`");
+            process::exit(1);
+        }
+    };
+
+    let config = match Config::load(Path::new(".")) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Failed to read configuration: {e}");
+            process::exit(1);
+        }
+    };
+
+    let clock = SystemClock;
+    let (year, day, unlock) = match puzzle_at(clock.now(), args.early) {
+        Puzzle::Day { year, day, unlock } => (year, day, unlock),
+        Puzzle::OutOfSeason { next } => {
+            println!(
+                "🎄 No puzzle today, the next one unlocks in {}.",
+                format_duration(next - clock.now())
+            );
+            return;
+        }
+    };
+    println!("🎄 Advent of Code {year}, day {day:02}");
+    wait_for_unlock(&clock, day, unlock, &mut io::stdout());
+
+    let day_padded = format!("{day:02}");
+    if !config.solution_path(day).exists() && !cargo(&["scaffold", &day_padded]) {
+        eprintln!("Failed to scaffold day {day_padded}.");
+        process::exit(1);
+    }
+
+    if Command::new("aoc").arg("-V").output().is_err() {
+        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+        process::exit(1);
+    }
+    let input_path = config.input_path(day);
+    let puzzle_path = Path::new("src/puzzles").join(format!("{day_padded}.md"));
+    if let Err(e) = std::fs::create_dir_all("src/puzzles") {
+        eprintln!("Failed to create the puzzles directory: {e}");
+        process::exit(1);
+    }
+    match with_backoff(&clock, FETCH_ATTEMPTS, || {
+        fetch(&config, year, day, &input_path, &puzzle_path)
+    }) {
+        Ok(()) => {
+            println!(
+                "🎄 Wrote input to \"{}\" and the description to \"{}\".",
+                input_path.display(),
+                puzzle_path.display()
+            )
+        }
+        Err(e) => {
+            eprintln!("Failed to download day {day_padded}: {e}");
+            process::exit(1);
+        }
+    }
+
+    if args.watch {
+        cargo(&["watch-day", &day_padded]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    /// Starts at `now` and only moves forward when slept.
+    struct FakeClock {
+        now: Cell<i64>,
+        slept: Cell<Duration>,
+    }
+
+    impl FakeClock {
+        fn at(now: i64) -> Self {
+            Self {
+                now: Cell::new(now),
+                slept: Cell::new(Duration::ZERO),
+            }
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> i64 {
+            self.now.get()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            self.now.set(self.now.get() + duration.as_secs() as i64);
+            self.slept.set(self.slept.get() + duration);
+        }
+    }
+
+    /// 2022-12-01T05:00:00Z, when day 1 of 2022 unlocked.
+    const DAY_ONE_2022: i64 = 1_669_870_800;
+
+    #[test]
+    fn finds_the_puzzle_in_utc_minus_5() {
+        let day = |day, unlock| Puzzle::Day {
+            year: 2022,
+            day,
+            unlock,
+        };
+        // 04:59 UTC on December 2 is still December 1 in UTC-5
+        let before_day_two = DAY_ONE_2022 + SECONDS_PER_DAY - 60;
+        assert_eq!(puzzle_at(before_day_two, 0), day(1, DAY_ONE_2022));
+        assert_eq!(
+            puzzle_at(before_day_two, 60),
+            day(2, DAY_ONE_2022 + SECONDS_PER_DAY)
+        );
+        assert_eq!(puzzle_at(DAY_ONE_2022 - 10, 60), day(1, DAY_ONE_2022));
+        assert_eq!(
+            puzzle_at(DAY_ONE_2022 - 10, 0),
+            Puzzle::OutOfSeason { next: DAY_ONE_2022 }
+        );
+        assert_eq!(
+            puzzle_at(unlock_time(2022, 25) + SECONDS_PER_DAY, 3600),
+            Puzzle::OutOfSeason {
+                next: unlock_time(2023, 1)
+            }
+        );
+    }
+
+    #[test]
+    fn counts_down() {
+        let clock = FakeClock::at(DAY_ONE_2022 - 3);
+        let mut out = Vec::new();
+        wait_for_unlock(&clock, 1, DAY_ONE_2022, &mut out);
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(clock.now(), DAY_ONE_2022);
+        assert!(out.starts_with("\r⏳ Day 01 unlocks in 00:00:03\r⏳ Day 01 unlocks in 00:00:02"));
+        assert!(out.contains("Day 01 is unlocked!"));
+        assert_eq!(
+            format_duration(SECONDS_PER_DAY * 2 + 3661),
+            "2 days 01:01:01"
+        );
+    }
+
+    #[test]
+    fn backs_off() {
+        let clock = FakeClock::at(0);
+        let mut calls = 0;
+        let result = with_backoff(&clock, 5, || {
+            calls += 1;
+            if calls < 4 {
+                Err("404".to_string())
+            } else {
+                Ok(())
+            }
+        });
+        assert_eq!(result, Ok(()));
+        assert_eq!(clock.slept.get(), Duration::from_secs(1 + 2 + 4));
+
+        let clock = FakeClock::at(0);
+        let result = with_backoff(&clock, 3, || Err("503".to_string()));
+        assert_eq!(result, Err("503".to_string()));
+        assert_eq!(clock.slept.get(), Duration::from_secs(1 + 2));
+    }
+}
@@ -0,0 +1,209 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use advent_of_code::config::Config;
+use advent_of_code::registry;
+
+struct Args {
+    days: Vec<u8>,
+    keep_input: bool,
+    dry_run: bool,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        keep_input: args.contains("--keep-input"),
+        dry_run: args.contains("--dry-run"),
+        days: args.free_from_fn(registry::parse_days)?,
+    })
+}
+
+/// The files of a day that exist: its module, its examples (`07.txt`, `07-2.txt`, ...) and,
+/// unless kept, its input and the vault's encrypted copy of it, which `read_file` would
+/// otherwise still decrypt. Recorded answers are always kept.
+fn day_files(
+    root: &Path,
+    config: &Config,
+    day: u8,
+    keep_input: bool,
+) -> Vec<(&'static str, PathBuf)> {
+    let mut files = vec![("module file", root.join(config.solution_path(day)))];
+    let mut examples = fs::read_dir(root.join(&config.examples))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+                return false;
+            };
+            let day_padded = format!("{day:02}");
+            let is_example = stem == day_padded
+                || stem
+                    .strip_prefix(&day_padded)
+                    .is_some_and(|rest| rest.starts_with('-'));
+            is_example && path.extension().is_some_and(|ext| ext == "txt")
+        })
+        .collect::<Vec<_>>();
+    examples.sort();
+    files.extend(examples.into_iter().map(|path| ("example file", path)));
+    if !keep_input {
+        files.push(("input file", root.join(config.input_path(day))));
+        files.push((
+            "encrypted input",
+            root.join(config.vault_path(&format!("{day:02}"))),
+        ));
+    }
+    files.retain(|(_, path)| path.is_file());
+    files
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {e}");
+            eprintln!("usage: `cargo unscaffold <days> [--keep-input] [--dry-run]`");
+            process::exit(1);
+        }
+    };
+
+    let config = match Config::load(Path::new(".")) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Failed to read configuration: {e}");
+            process::exit(1);
+        }
+    };
+
+    let files = args
+        .days
+        .iter()
+        .flat_map(|&day| day_files(Path::new(""), &config, day, args.keep_input))
+        .collect::<Vec<_>>();
+    let edits = match registry::edits(Path::new(""), &args.days, false) {
+        Ok(edits) => edits,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    if files.is_empty() && edits.is_empty() {
+        println!("🎄 Nothing to do, no file or registration left.");
+        return;
+    }
+
+    if args.dry_run {
+        for (kind, path) in &files {
+            println!("Would remove {kind} \"{}\"", path.display());
+        }
+        for edit in &edits {
+            println!("Would update {} \"{}\"", edit.kind, edit.path.display());
+        }
+        return;
+    }
+
+    // read everything first, so a failure halfway can put back what was removed
+    let originals = match files
+        .iter()
+        .map(|(_, path)| fs::read(path))
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(originals) => originals,
+        Err(e) => {
+            eprintln!("Failed to read the day's files: {e}");
+            process::exit(1);
+        }
+    };
+
+    let removed = files.iter().map(|(_, path)| fs::remove_file(path));
+    let updated = edits
+        .iter()
+        .map(|edit| fs::write(&edit.path, &edit.contents));
+    if let Some(e) = removed.chain(updated).find_map(Result::err) {
+        for ((_, path), original) in files.iter().zip(&originals) {
+            #[allow(unused_must_use)]
+            {
+                fs::write(path, original);
+            }
+        }
+        for edit in &edits {
+            #[allow(unused_must_use)]
+            {
+                fs::write(&edit.path, &edit.original);
+            }
+        }
+        eprintln!("Failed to unscaffold: {e}");
+        eprintln!("Rolled back, no files were removed or changed.");
+        process::exit(1);
+    }
+
+    for (kind, path) in &files {
+        println!("Removed {kind} \"{}\"", path.display());
+    }
+    for edit in &edits {
+        println!("Updated {} \"{}\"", edit.kind, edit.path.display());
+    }
+    println!("---");
+    println!(
+        "🎄 Recorded answers in \"{}\" were kept.",
+        config.answers.display()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_day_files() {
+        let root = std::env::temp_dir().join(format!("unscaffold-{}", process::id()));
+        let config = Config::default();
+        for dir in [
+            &config.solutions,
+            &config.inputs,
+            &config.examples,
+            &config.vault,
+        ] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        for file in [
+            "src/bin/07.rs",
+            "src/inputs/07.txt",
+            "src/vault/07.txt.enc",
+            "src/vault/17.txt.enc",
+            "src/examples/07.txt",
+            "src/examples/07-2.txt",
+            "src/examples/07x.txt",
+            "src/examples/17.txt",
+        ] {
+            fs::write(root.join(file), "").unwrap();
+        }
+
+        let names = |keep_input| {
+            day_files(&root, &config, 7, keep_input)
+                .into_iter()
+                .map(|(kind, path)| (kind, path.strip_prefix(&root).unwrap().to_path_buf()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            names(false),
+            [
+                ("module file", PathBuf::from("src/bin/07.rs")),
+                ("example file", PathBuf::from("src/examples/07-2.txt")),
+                ("example file", PathBuf::from("src/examples/07.txt")),
+                ("input file", PathBuf::from("src/inputs/07.txt")),
+                ("encrypted input", PathBuf::from("src/vault/07.txt.enc")),
+            ]
+        );
+        assert_eq!(names(true).len(), 3);
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,325 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+
+use advent_of_code::config::Config;
+use advent_of_code::vault::{self, Key};
+
+const USAGE: &str = "usage: `cargo vault <encrypt|decrypt|install-hook> [--force]`";
+
+/// Runs on checkout and after a pull, so new encrypted inputs are decrypted right away.
+const HOOK: &str = "#!/bin/sh\n# installed by `cargo vault install-hook`\ncargo vault decrypt\n";
+const HOOKS: [&str; 2] = ["post-checkout", "post-merge"];
+
+struct Args {
+    command: String,
+    force: bool,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        force: args.contains("--force"),
+        command: args
+            .subcommand()?
+            .ok_or(pico_args::Error::MissingArgument)?,
+    })
+}
+
+/// What happened to one input.
+#[derive(Debug, PartialEq, Eq)]
+enum Change {
+    Written,
+    Unchanged,
+    /// Decrypting would overwrite an input that differs from the vault's copy.
+    Kept,
+    Failed(String),
+}
+
+/// The names of the files in `dir` ending in `suffix`, such as `"07"` for `07.txt`.
+fn names(dir: &Path, suffix: &str) -> Vec<String> {
+    let mut names = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            name.strip_suffix(suffix).map(str::to_string)
+        })
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+fn write(path: &Path, contents: &[u8]) -> Change {
+    let written = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(path, contents));
+    match written {
+        Ok(()) => Change::Written,
+        Err(e) => Change::Failed(format!("could not write \"{}\": {e}", path.display())),
+    }
+}
+
+/// Encrypts every non-empty input under `root` into the vault.
+fn encrypt_all(root: &Path, config: &Config, key: &Key) -> Vec<(String, Change)> {
+    names(&root.join(&config.inputs), ".txt")
+        .into_iter()
+        .filter_map(|name| {
+            let input = root.join(&config.inputs).join(format!("{name}.txt"));
+            let change = match fs::read_to_string(&input) {
+                Ok(text) if text.trim().is_empty() => return None,
+                Ok(text) => {
+                    let path = root.join(config.vault_path(&name));
+                    let data = vault::encrypt(key, &name, &text);
+                    match fs::read(&path) {
+                        Ok(existing) if existing == data => Change::Unchanged,
+                        _ => write(&path, &data),
+                    }
+                }
+                Err(e) => Change::Failed(format!("could not read \"{}\": {e}", input.display())),
+            };
+            Some((name, change))
+        })
+        .collect()
+}
+
+/// Decrypts every input in the vault under `root`. Inputs that differ from the vault's copy
+/// are only overwritten with `force`.
+fn decrypt_all(root: &Path, config: &Config, key: &Key, force: bool) -> Vec<(String, Change)> {
+    names(&root.join(&config.vault), ".txt.enc")
+        .into_iter()
+        .map(|name| {
+            let path = root.join(config.vault_path(&name));
+            let text = match fs::read(&path) {
+                Ok(data) => vault::decrypt(key, &name, &data).map_err(|e| e.to_string()),
+                Err(e) => Err(format!("could not read \"{}\": {e}", path.display())),
+            };
+            let input = root.join(&config.inputs).join(format!("{name}.txt"));
+            let change = match (text, fs::read_to_string(&input)) {
+                (Err(e), _) => Change::Failed(e),
+                (Ok(text), Ok(existing)) if existing == text => Change::Unchanged,
+                (Ok(_), Ok(existing)) if !force && !existing.trim().is_empty() => Change::Kept,
+                (Ok(text), _) => write(&input, text.as_bytes()),
+            };
+            (name, change)
+        })
+        .collect()
+}
+
+/// The repository's hooks directory. It is only `.git/hooks` in a plain clone: in a worktree or
+/// a submodule `.git` is a file, and `core.hooksPath` can move it elsewhere.
+fn hooks_dir(root: &Path) -> Result<PathBuf, String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--git-path", "hooks"])
+        // an empty `root` is the current directory, which `current_dir` doesn't accept
+        .current_dir(root.join("."))
+        .output()
+        .map_err(|e| format!("could not run git: {e}"))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    let hooks = String::from_utf8_lossy(&output.stdout);
+    Ok(root.join(hooks.trim()))
+}
+
+/// Installs the git hooks that decrypt the inputs, keeping any other hook already there.
+fn install_hooks(hooks: &Path) -> Result<Vec<PathBuf>, String> {
+    fs::create_dir_all(hooks)
+        .map_err(|e| format!("could not create \"{}\": {e}", hooks.display()))?;
+    let mut installed = Vec::new();
+    for hook in HOOKS {
+        let path = hooks.join(hook);
+        match fs::read_to_string(&path) {
+            Ok(existing) if existing == HOOK => continue,
+            Ok(_) => {
+                return Err(format!(
+                    "\"{}\" already exists, add `cargo vault decrypt` to it by hand",
+                    path.display()
+                ))
+            }
+            Err(_) => {}
+        }
+        fs::write(&path, HOOK)
+            .map_err(|e| format!("could not write \"{}\": {e}", path.display()))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755))
+                .map_err(|e| format!("could not make \"{}\" executable: {e}", path.display()))?;
+        }
+        installed.push(path);
+    }
+    Ok(installed)
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {e}");
+            eprintln!("{USAGE}");
+            process::exit(1);
+        }
+    };
+
+    let root = Path::new("");
+    let config = match Config::load(Path::new(".")) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Failed to read configuration: {e}");
+            process::exit(1);
+        }
+    };
+
+    if args.command == "install-hook" {
+        match hooks_dir(root).and_then(|hooks| install_hooks(&hooks)) {
+            Ok(installed) => {
+                for path in installed {
+                    println!("Installed \"{}\"", path.display());
+                }
+                println!("🎄 Inputs will be decrypted on checkout and after a pull.");
+                return;
+            }
+            Err(e) => {
+                eprintln!("Failed to install hooks: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    if !["encrypt", "decrypt"].contains(&args.command.as_str()) {
+        eprintln!("Unknown command `{}`", args.command);
+        eprintln!("{USAGE}");
+        process::exit(1);
+    }
+
+    let key = match Key::load(root, &config) {
+        Ok(key) => key,
+        Err(e) => {
+            eprintln!("Failed to load vault key: {e}");
+            process::exit(1);
+        }
+    };
+    let changes = match args.command.as_str() {
+        "encrypt" => encrypt_all(root, &config, &key),
+        _ => decrypt_all(root, &config, &key, args.force),
+    };
+
+    let mut failed = false;
+    for (name, change) in &changes {
+        match change {
+            Change::Written if args.command == "encrypt" => println!("Encrypted {name}"),
+            Change::Written => println!("Decrypted {name}"),
+            Change::Unchanged => {}
+            Change::Kept => println!("Kept {name}, it differs from the vault (use --force)"),
+            Change::Failed(e) => {
+                eprintln!("Failed {name}: {e}");
+                failed = true;
+            }
+        }
+    }
+    println!("---");
+    let written = changes
+        .iter()
+        .filter(|(_, change)| *change == Change::Written)
+        .count();
+    match args.command.as_str() {
+        "encrypt" => println!(
+            "🎄 Encrypted {written} of {} inputs into \"{}\".",
+            changes.len(),
+            config.vault.display()
+        ),
+        _ => println!(
+            "🎄 Decrypted {written} of {} inputs into \"{}\".",
+            changes.len(),
+            config.inputs.display()
+        ),
+    }
+    if failed {
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypts_and_decrypts_inputs() {
+        let root = std::env::temp_dir().join(format!("vault-{}", process::id()));
+        let config = Config::default();
+        fs::create_dir_all(root.join(&config.inputs)).unwrap();
+        fs::write(root.join("src/inputs/01.txt"), "1\n2\n").unwrap();
+        fs::write(root.join("src/inputs/02.txt"), "").unwrap();
+        fs::write(root.join("src/inputs/09-2.txt"), "R 4\n").unwrap();
+        let key =
+            Key::new("0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef").unwrap();
+
+        let changes = |changes: Vec<(String, Change)>| {
+            changes
+                .into_iter()
+                .map(|(name, change)| format!("{name} {change:?}"))
+                .collect::<Vec<_>>()
+        };
+        let encrypted = changes(encrypt_all(&root, &config, &key));
+        assert_eq!(encrypted, ["01 Written", "09-2 Written"]);
+        let again = changes(encrypt_all(&root, &config, &key));
+        assert_eq!(again, ["01 Unchanged", "09-2 Unchanged"]);
+
+        fs::remove_file(root.join("src/inputs/01.txt")).unwrap();
+        fs::write(root.join("src/inputs/09-2.txt"), "edited").unwrap();
+        let decrypted = changes(decrypt_all(&root, &config, &key, false));
+        assert_eq!(decrypted, ["01 Written", "09-2 Kept"]);
+        assert_eq!(
+            fs::read_to_string(root.join("src/inputs/01.txt")).unwrap(),
+            "1\n2\n"
+        );
+        let forced = changes(decrypt_all(&root, &config, &key, true));
+        assert_eq!(forced, ["01 Unchanged", "09-2 Written"]);
+
+        let wrong = decrypt_all(
+            &root,
+            &config,
+            &Key::new("fedcba9876543210fedcba9876543210fedcba9876543210fedcba9876543210").unwrap(),
+            true,
+        );
+        assert!(wrong.iter().all(|(_, c)| matches!(c, Change::Failed(_))));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn installs_hooks_where_git_keeps_them() {
+        let root = std::env::temp_dir().join(format!("vault-hooks-{}", process::id()));
+        let work_tree = root.join("work");
+        // like a worktree or submodule, `.git` is a file pointing at the repository
+        let status = Command::new("git")
+            .args(["init", "--quiet", "--separate-git-dir"])
+            .arg(root.join("repo.git"))
+            .arg(&work_tree)
+            .status()
+            .unwrap();
+        assert!(status.success());
+        assert!(work_tree.join(".git").is_file());
+
+        let hooks = hooks_dir(&work_tree).unwrap();
+        assert_eq!(
+            hooks.canonicalize().unwrap(),
+            root.join("repo.git/hooks").canonicalize().unwrap()
+        );
+        let installed = install_hooks(&hooks).unwrap();
+        assert_eq!(installed, HOOKS.map(|hook| hooks.join(hook)));
+        assert!(install_hooks(&hooks).unwrap().is_empty());
+
+        fs::write(hooks.join("post-merge"), "#!/bin/sh\necho mine\n").unwrap();
+        assert!(install_hooks(&hooks)
+            .unwrap_err()
+            .contains("already exists"));
+        fs::remove_dir_all(root).unwrap();
+    }
+}
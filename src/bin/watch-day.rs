@@ -0,0 +1,373 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Output};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use advent_of_code::config::Config;
+use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+const ANSI_RED: &str = "\x1b[31m";
+const ANSI_GREEN: &str = "\x1b[32m";
+
+struct Args {
+    day: u8,
+    interval: u64,
+    once: bool,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        once: args.contains("--once"),
+        interval: args.opt_value_from_str("--interval")?.unwrap_or(500),
+        day: args.free_from_str()?,
+    })
+}
+
+/// Modification time and size of every watched file that exists.
+type Snapshot = BTreeMap<PathBuf, (SystemTime, u64)>;
+
+/// The day's solution, examples and input, and the shared helpers.
+fn watched_files(root: &Path, config: &Config, day: u8) -> Vec<PathBuf> {
+    let day_padded = format!("{day:02}");
+    let src = root.join("src");
+    let mut files = vec![
+        root.join(config.solution_path(day)),
+        root.join(config.input_path(day)),
+        src.join("helpers.rs"),
+    ];
+    let listed = |dir: PathBuf, keep: &dyn Fn(&str) -> bool| -> Vec<PathBuf> {
+        fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.file_name().and_then(|n| n.to_str()).is_some_and(keep))
+            .collect()
+    };
+    // additional examples are named like `09-2.txt`
+    files.extend(listed(root.join(&config.examples), &|name| {
+        name.starts_with(&day_padded) && name.ends_with(".txt")
+    }));
+    files.extend(listed(src.join("helpers"), &|name| name.ends_with(".rs")));
+    files
+}
+
+fn snapshot(files: &[PathBuf]) -> Snapshot {
+    files
+        .iter()
+        .filter_map(|path| {
+            let meta = fs::metadata(path).ok()?;
+            Some((path.clone(), (meta.modified().ok()?, meta.len())))
+        })
+        .collect()
+}
+
+/// Files that were added, removed or modified between two snapshots.
+fn changed(before: &Snapshot, after: &Snapshot) -> Vec<PathBuf> {
+    let paths = before.keys().chain(after.keys()).collect::<BTreeSet<_>>();
+    paths
+        .into_iter()
+        .filter(|path| before.get(*path) != after.get(*path))
+        .cloned()
+        .collect()
+}
+
+/// Counts from the `test result:` lines of `cargo test` output.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct TestSummary {
+    passed: u32,
+    failed: u32,
+    ignored: u32,
+}
+
+fn parse_test_summary(output: &str) -> Option<TestSummary> {
+    let mut summary = None;
+    for line in output.lines() {
+        let Some(counts) = line.strip_prefix("test result: ") else {
+            continue;
+        };
+        let summary = summary.get_or_insert_with(TestSummary::default);
+        for count in counts.split(';') {
+            let mut words = count.split_whitespace().rev();
+            let (Some(name), Some(n)) = (words.next(), words.next()) else {
+                continue;
+            };
+            let Ok(n) = n.parse::<u32>() else {
+                continue;
+            };
+            match name {
+                "passed" => summary.passed += n,
+                "failed" => summary.failed += n,
+                "ignored" => summary.ignored += n,
+                _ => {}
+            }
+        }
+    }
+    summary
+}
+
+/// Failed test names and their panic messages from `cargo test` output.
+fn test_failures(output: &str) -> Vec<String> {
+    let lines = output.lines().collect::<Vec<_>>();
+    let mut failures = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        if line.starts_with("test ") && line.ends_with("FAILED") {
+            failures.push(line.to_string());
+        } else if line.contains("panicked at") {
+            // the message follows the location, assertions add `left:` and `right:`
+            failures.extend(
+                lines[i + 1..]
+                    .iter()
+                    .take_while(|l| !l.is_empty() && !l.starts_with("stack backtrace"))
+                    .take(4)
+                    .map(|l| format!("    {l}")),
+            );
+        }
+    }
+    failures
+}
+
+/// The compiler errors from a failed build, without the warnings before them.
+fn build_errors(stderr: &str) -> String {
+    let errors = stderr
+        .lines()
+        .skip_while(|line| !line.starts_with("error"))
+        .collect::<Vec<_>>();
+    errors.join("\n")
+}
+
+fn cargo(args: &[&str]) -> Output {
+    // only read by the solution when it runs, for exact timings
+    let command = Command::new("cargo")
+        .args(args)
+        .env(advent_of_code::REPORT_VAR, "1")
+        .output();
+    match command {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("Failed to run cargo: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn ok(text: &str) -> String {
+    format!("{ANSI_GREEN}✔ {text}{ANSI_RESET}")
+}
+
+fn failed(text: &str) -> String {
+    format!("{ANSI_RED}✘ {text}{ANSI_RESET}")
+}
+
+/// Builds the day, runs its tests and, if they pass and there is an input, solves it.
+fn run_cycle(config: &Config, day: u8) {
+    let bin = format!("{day:02}");
+    let input = config.input_path(day);
+
+    let build = cargo(&["build", "--color", "never", "--bin", &bin]);
+    if !build.status.success() {
+        println!("  {}", failed("build"));
+        println!("{}", build_errors(&String::from_utf8_lossy(&build.stderr)));
+        return;
+    }
+
+    let test = cargo(&["test", "--color", "never", "--bin", &bin]);
+    let output = String::from_utf8_lossy(&test.stdout);
+    let summary = parse_test_summary(&output).unwrap_or_default();
+    let tests = format!(
+        "tests: {} passed, {} failed, {} ignored",
+        summary.passed, summary.failed, summary.ignored
+    );
+    if !test.status.success() {
+        println!("  {}  {}", ok("build"), failed(&tests));
+        for failure in test_failures(&output) {
+            println!("  {failure}");
+        }
+        return;
+    }
+
+    let solution = match input.exists() {
+        true => solve(&bin),
+        false => format!("{ANSI_ITALIC}no input at {}{ANSI_RESET}", input.display()),
+    };
+    println!("  {}  {}  {solution}", ok("build"), ok(&tests));
+}
+
+fn solve(bin: &str) -> String {
+    let run = cargo(&["run", "--color", "never", "--bin", bin]);
+    let output = String::from_utf8_lossy(&run.stdout);
+    if !run.status.success() {
+        let stderr = String::from_utf8_lossy(&run.stderr);
+        let failure = advent_of_code::parse_failure(&stderr);
+        return failed(failure.as_deref().unwrap_or("solution failed"));
+    }
+    let answers = advent_of_code::parse_answers(&output);
+    let times = advent_of_code::parse_exec_times(&output);
+    let part = |n: usize, time: Duration| match &answers[n - 1] {
+        Some(answer) => format!("part {n}: {ANSI_BOLD}{answer}{ANSI_RESET} ({time:.2?})"),
+        None => format!("part {n}: not solved"),
+    };
+    format!("{}  {}", part(1, times.part_one), part(2, times.part_two))
+}
+
+/// The current UTC time as `HH:MM:SS`.
+fn clock() -> String {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600 % 24,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {e}");
+            eprintln!("usage: `cargo watch-day <day> [--interval <ms>] [--once]`");
+            process::exit(1);
+        }
+    };
+
+    let root = Path::new(".");
+    let config = match Config::load(root) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Failed to read configuration: {e}");
+            process::exit(1);
+        }
+    };
+    let interval = Duration::from_millis(args.interval);
+
+    println!("🎄 Watching day {:02}, press Ctrl+C to stop.", args.day);
+    println!("[{}] {ANSI_BOLD}start{ANSI_RESET}", clock());
+    run_cycle(&config, args.day);
+    if args.once {
+        return;
+    }
+
+    let mut last = snapshot(&watched_files(root, &config, args.day));
+    loop {
+        thread::sleep(interval);
+        let current = snapshot(&watched_files(root, &config, args.day));
+        if current == last {
+            continue;
+        }
+        // editors often write a file in several steps, wait until it settles
+        thread::sleep(interval);
+        let settled = snapshot(&watched_files(root, &config, args.day));
+        let files = changed(&last, &settled)
+            .iter()
+            .map(|path| {
+                path.strip_prefix(root)
+                    .unwrap_or(path)
+                    .display()
+                    .to_string()
+            })
+            .collect::<Vec<_>>();
+        last = settled;
+        if files.is_empty() {
+            continue;
+        }
+        println!(
+            "[{}] {ANSI_BOLD}{}{ANSI_RESET} changed",
+            clock(),
+            files.join(", ")
+        );
+        run_cycle(&config, args.day);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_changes() {
+        let dir = std::env::temp_dir().join(format!("watch-day-{}", process::id()));
+        fs::create_dir_all(dir.join("src/examples")).unwrap();
+        fs::create_dir_all(dir.join("src/bin")).unwrap();
+        fs::write(dir.join("src/bin/09.rs"), "fn main() {}").unwrap();
+        fs::write(dir.join("src/examples/09.txt"), "R 4").unwrap();
+        fs::write(dir.join("src/examples/10.txt"), "noop").unwrap();
+
+        let files = watched_files(&dir, &Config::default(), 9);
+        assert!(files.contains(&dir.join("src/examples/09.txt")));
+        assert!(!files.contains(&dir.join("src/examples/10.txt")));
+
+        let before = snapshot(&files);
+        assert_eq!(before.len(), 2);
+        fs::write(dir.join("src/examples/09-2.txt"), "R 5").unwrap();
+        fs::write(dir.join("src/examples/09.txt"), "R 4\nU 4").unwrap();
+        let after = snapshot(&watched_files(&dir, &Config::default(), 9));
+        assert_eq!(
+            changed(&before, &after),
+            [
+                dir.join("src/examples/09-2.txt"),
+                dir.join("src/examples/09.txt")
+            ]
+        );
+        assert!(changed(&after, &after).is_empty());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn summarizes_tests() {
+        let output = "running 3 tests
+test examples::part_one ... ok
+test examples::part_two ... FAILED
+test tests::slow ... ignored
+
+failures:
+
+---- examples::part_two stdout ----
+
+thread 'examples::part_two' panicked at src/bin/09.rs:10:1:
+assertion `left == right` failed
+  left: Some(2)
+ right: Some(1)
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+test result: FAILED. 1 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out; finished in 0.01s
+";
+        assert_eq!(
+            parse_test_summary(output),
+            Some(TestSummary {
+                passed: 1,
+                failed: 1,
+                ignored: 1
+            })
+        );
+        assert_eq!(
+            test_failures(output),
+            [
+                "test examples::part_two ... FAILED",
+                "    assertion `left == right` failed",
+                "      left: Some(2)",
+                "     right: Some(1)",
+                "    note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace",
+            ]
+        );
+        assert_eq!(parse_test_summary("error: no bin target"), None);
+    }
+
+    #[test]
+    fn keeps_only_build_errors() {
+        let stderr = "warning: unused variable\n  --> src/bin/09.rs:1:1\n\nerror[E0308]: mismatched types\n  --> src/bin/09.rs:2:1\n";
+        assert_eq!(
+            build_errors(stderr),
+            "error[E0308]: mismatched types\n  --> src/bin/09.rs:2:1"
+        );
+    }
+}
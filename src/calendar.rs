@@ -0,0 +1,84 @@
+/*
+ * Dates of the event: puzzles unlock every midnight from December 1 to 25 in UTC-5.
+ * Example import: `use advent_of_code::calendar::{self, unlock_time};`.
+ */
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Puzzles unlock at midnight in UTC-5.
+pub const UNLOCK_OFFSET: i64 = -5 * 3600;
+pub const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Seconds since the unix epoch.
+pub fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64)
+}
+
+/// The `(year, month, day)` of a day counted from 1970-01-01.
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Days from 1970-01-01 to December `day` of `year`.
+pub fn days_from_december(year: i64, day: u32) -> i64 {
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    // December is month 9 counting from March
+    let doy = (153 * 9 + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// The `(year, month, day)` in UTC-5 at the unix time `now`.
+pub fn local_date(now: i64) -> (i64, u32, u32) {
+    civil_from_days((now + UNLOCK_OFFSET).div_euclid(SECONDS_PER_DAY))
+}
+
+/// The unix time at which a puzzle unlocks.
+pub fn unlock_time(year: i64, day: u8) -> i64 {
+    days_from_december(year, u32::from(day)) * SECONDS_PER_DAY - UNLOCK_OFFSET
+}
+
+/// The year of the most recent event that has started at the unix time `now`.
+pub fn latest_event(now: i64) -> i64 {
+    match local_date(now) {
+        (year, 12, _) => year,
+        (year, _, _) => year - 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2022-12-01T05:00:00Z, when day 1 of 2022 unlocked.
+    const DAY_ONE_2022: i64 = 1_669_870_800;
+
+    #[test]
+    fn converts_dates() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(19_327), (2022, 12, 1));
+        assert_eq!(days_from_december(2022, 1), 19_327);
+        assert_eq!(days_from_december(2023, 25), 19_716);
+        assert_eq!(unlock_time(2022, 1), DAY_ONE_2022);
+        assert_eq!(local_date(DAY_ONE_2022 - 1), (2022, 11, 30));
+    }
+
+    #[test]
+    fn finds_latest_event() {
+        assert_eq!(latest_event(DAY_ONE_2022 - 1), 2021);
+        assert_eq!(latest_event(DAY_ONE_2022), 2022);
+        assert_eq!(latest_event(unlock_time(2023, 1) - 1), 2022);
+    }
+}
@@ -0,0 +1,335 @@
+/*
+ * Project settings from `aoc.toml` at the crate root, shared by the template binaries and `read_file`.
+ * Example import: `use advent_of_code::config::Config;`.
+ */
+use std::env;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+pub const FILE_NAME: &str = "aoc.toml";
+
+/// Settings for the project. Every key of `aoc.toml` is optional, and the defaults are the
+/// layout of this template. Paths are relative to the crate root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// The year to download from, aoc-cli picks the latest if unset.
+    pub year: Option<i16>,
+    pub solutions: PathBuf,
+    pub inputs: PathBuf,
+    pub examples: PathBuf,
+    pub answers: PathBuf,
+    /// The file holding the session cookie, aoc-cli looks in its default location if unset.
+    pub session: Option<PathBuf>,
+    /// A template to scaffold new days from, or a directory of named templates.
+    pub template: PathBuf,
+    /// The type of both answers in a scaffolded day.
+    pub answer_type: String,
+    /// Sent with every request of `cargo download --all`, so the site knows who to contact.
+    /// Unset, `--all` refuses to run.
+    pub user_agent: Option<String>,
+    /// Seconds to wait between two requests of `cargo download --all`.
+    pub delay: u64,
+    /// Where `cargo vault` keeps the encrypted inputs, which are safe to commit.
+    pub vault: PathBuf,
+    /// The file holding the vault key, used when `AOC_VAULT_KEY` is unset.
+    pub key_file: PathBuf,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            year: None,
+            solutions: PathBuf::from("src/bin"),
+            inputs: PathBuf::from("src/inputs"),
+            examples: PathBuf::from("src/examples"),
+            answers: PathBuf::from("src/answers"),
+            session: None,
+            template: PathBuf::from("templates"),
+            answer_type: "u32".to_string(),
+            user_agent: None,
+            delay: 5,
+            vault: PathBuf::from("src/vault"),
+            key_file: PathBuf::from(".vault-key"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ConfigError {
+    Io(String),
+    /// Invalid TOML, or a key or value the settings don't have, on a line of the file.
+    Syntax {
+        line: usize,
+        message: String,
+    },
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(e) => write!(f, "could not read {FILE_NAME}: {e}"),
+            ConfigError::Syntax { line, message } => write!(f, "{FILE_NAME}:{line}: {message}"),
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
+/// The layout of `aoc.toml`. Every key is optional and unknown keys are errors, to catch typos.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct File {
+    year: Option<i16>,
+    #[serde(default)]
+    paths: Paths,
+    #[serde(default)]
+    scaffold: Scaffold,
+    #[serde(default)]
+    download: Download,
+    #[serde(default)]
+    vault: Vault,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Paths {
+    solutions: Option<PathBuf>,
+    inputs: Option<PathBuf>,
+    examples: Option<PathBuf>,
+    answers: Option<PathBuf>,
+    session: Option<PathBuf>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Scaffold {
+    template: Option<PathBuf>,
+    answer_type: Option<String>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Download {
+    user_agent: Option<String>,
+    delay: Option<u64>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Vault {
+    path: Option<PathBuf>,
+    key_file: Option<PathBuf>,
+}
+
+impl Config {
+    /// Reads the settings from TOML: a top-level `year`, and `[paths]`, `[scaffold]`,
+    /// `[download]` and `[vault]` tables. Unknown keys are errors, to catch typos.
+    pub fn parse(text: &str) -> Result<Self, ConfigError> {
+        let file: File = toml::from_str(text).map_err(|e| ConfigError::Syntax {
+            line: e
+                .span()
+                .map_or(1, |span| text[..span.start].matches('\n').count() + 1),
+            message: e.message().trim().replace('\n', ", "),
+        })?;
+        let defaults = Config::default();
+        Ok(Config {
+            year: file.year,
+            solutions: file.paths.solutions.unwrap_or(defaults.solutions),
+            inputs: file.paths.inputs.unwrap_or(defaults.inputs),
+            examples: file.paths.examples.unwrap_or(defaults.examples),
+            answers: file.paths.answers.unwrap_or(defaults.answers),
+            session: file.paths.session,
+            template: file.scaffold.template.unwrap_or(defaults.template),
+            answer_type: file.scaffold.answer_type.unwrap_or(defaults.answer_type),
+            user_agent: file.download.user_agent,
+            delay: file.download.delay.unwrap_or(defaults.delay),
+            vault: file.vault.path.unwrap_or(defaults.vault),
+            key_file: file.vault.key_file.unwrap_or(defaults.key_file),
+        })
+    }
+
+    /// Reads `aoc.toml` in `root`, or returns the defaults if there is none.
+    pub fn load(root: &Path) -> Result<Self, ConfigError> {
+        match fs::read_to_string(root.join(FILE_NAME)) {
+            Ok(text) => Self::parse(&text),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(ConfigError::Io(e.to_string())),
+        }
+    }
+
+    /// The directory `read_file` reads a folder such as `"inputs"` from.
+    pub fn folder(&self, folder: &str) -> PathBuf {
+        match folder {
+            "inputs" => self.inputs.clone(),
+            "examples" => self.examples.clone(),
+            "answers" => self.answers.clone(),
+            "bin" => self.solutions.clone(),
+            folder => Path::new("src").join(folder),
+        }
+    }
+
+    pub fn solution_path(&self, day: u8) -> PathBuf {
+        self.solutions.join(format!("{day:02}.rs"))
+    }
+
+    pub fn input_path(&self, day: u8) -> PathBuf {
+        self.inputs.join(format!("{day:02}.txt"))
+    }
+
+    pub fn example_path(&self, day: u8) -> PathBuf {
+        self.examples.join(format!("{day:02}.txt"))
+    }
+
+    pub fn answer_path(&self, day: u8) -> PathBuf {
+        self.answers.join(format!("{day:02}.txt"))
+    }
+
+    /// The encrypted copy of an input such as `"07"`.
+    pub fn vault_path(&self, name: &str) -> PathBuf {
+        self.vault.join(format!("{name}.txt.enc"))
+    }
+
+    /// The session cookie file, with a leading `~` expanded to the home directory.
+    pub fn session_file(&self) -> Option<PathBuf> {
+        self.session.as_deref().map(expand_home)
+    }
+
+    /// The vault key file, with a leading `~` expanded to the home directory.
+    pub fn key_path(&self) -> PathBuf {
+        expand_home(&self.key_file)
+    }
+}
+
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), env::var_os("HOME")) {
+        (Ok(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// The settings in the current directory's `aoc.toml`, read once.
+///
+/// # Panics
+///
+/// If the file exists but is invalid.
+pub fn get() -> &'static Config {
+    static CONFIG: OnceLock<Config> = OnceLock::new();
+    CONFIG.get_or_init(|| {
+        let root = env::current_dir().unwrap();
+        Config::load(&root).unwrap_or_else(|e| panic!("{e}"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checked_in_file_has_the_defaults() {
+        let text = include_str!("../aoc.toml");
+        assert_eq!(Config::parse(text), Ok(Config::default()));
+        assert_eq!(Config::parse(""), Ok(Config::default()));
+    }
+
+    #[test]
+    fn parses_every_key() {
+        let text = r#"
+            year = 2_021 # the last one
+
+            [paths]
+            solutions = "solutions"
+            inputs = 'C:\inputs'
+            examples = "ex#amples"
+            answers = "answers/\"quoted\""
+            session = "~/.config/aoc/session"
+
+            [scaffold]
+            template = "templates/day.rs"
+            answer_type = "u64"
+
+            [download]
+            user_agent = "me@example.com"
+            delay = 10
+
+            [vault]
+            path = "vault"
+            key_file = "~/.aoc-vault-key"
+        "#;
+        let config = Config::parse(text).unwrap();
+        assert_eq!(config.year, Some(2021));
+        assert_eq!(config.solution_path(7), Path::new("solutions/07.rs"));
+        assert_eq!(config.inputs, Path::new(r"C:\inputs"));
+        assert_eq!(config.folder("examples"), Path::new("ex#amples"));
+        assert_eq!(config.answers, Path::new("answers/\"quoted\""));
+        assert_eq!(config.folder("other"), Path::new("src/other"));
+        assert_eq!(config.template, Path::new("templates/day.rs"));
+        assert_eq!(config.answer_type, "u64");
+        assert_eq!(
+            (config.user_agent.as_deref(), config.delay),
+            (Some("me@example.com"), 10)
+        );
+        assert_eq!(config.vault_path("07"), Path::new("vault/07.txt.enc"));
+        if let Some(home) = env::var_os("HOME") {
+            assert_eq!(
+                config.session_file(),
+                Some(Path::new(&home).join(".config/aoc/session"))
+            );
+            assert_eq!(config.key_path(), Path::new(&home).join(".aoc-vault-key"));
+        }
+    }
+
+    #[test]
+    fn reports_errors_with_line() {
+        let error = |text| match Config::parse(text) {
+            Err(ConfigError::Syntax { line, message }) => (line, message),
+            other => panic!("expected an error, got {other:?}"),
+        };
+        assert_eq!(
+            error("year = \"2022\""),
+            (1, "invalid type: string \"2022\", expected i16".into())
+        );
+        assert_eq!(
+            error("year = 40000"),
+            (1, "invalid value: integer `40000`, expected i16".into())
+        );
+        assert_eq!(
+            error("\n[path]"),
+            (
+                2,
+                "unknown field `path`, expected one of `year`, `paths`, `scaffold`, `download`, `vault`"
+                    .into()
+            )
+        );
+        assert_eq!(
+            error("[paths]\ninput = \"in\""),
+            (
+                2,
+                "unknown field `input`, expected one of `solutions`, `inputs`, `examples`, `answers`, `session`"
+                    .into()
+            )
+        );
+        assert_eq!(
+            error("[scaffold]\nanswer_type = u64"),
+            (2, "invalid string, expected `\"`, `'`".into())
+        );
+        assert_eq!(error("year"), (1, "expected `.`, `=`".into()));
+        assert_eq!(
+            error("[download]\ndelay = -1"),
+            (2, "invalid value: integer `-1`, expected u64".into())
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_toml() {
+        assert!(Config::parse("year = 2__0").is_err());
+        assert!(Config::parse("[paths]\ninputs = \"in\"puts\"").is_err());
+        assert!(Config::parse("[paths]\ninputs = \"in\\qputs\"").is_err());
+        assert!(Config::parse("[paths]\n[paths]").is_err());
+    }
+}
@@ -0,0 +1,11 @@
+/*
+ * The solution registry: every day with a solution in `src/bin`.
+ * This file is kept up to date by `cargo scaffold` and `cargo unscaffold`.
+ */
+
+/// Every scaffolded day, in order.
+#[rustfmt::skip]
+pub const DAYS: &[u8] = &[
+    1, 2, 3, 4, 5, 6, 7, 8, 9, 10,
+    11, 12, 13, 14, 15, 16, 18, 19, 24,
+];
@@ -0,0 +1,212 @@
+/*
+ * Differential testing: checks a day's fast solvers against slow reference solvers.
+ * Example import: `use advent_of_code::differential::Reference;`.
+ */
+use std::fmt::{self, Display};
+
+use crate::{Params, Solution};
+
+/// Naive but obviously correct solvers for a [`Solution`], e.g. brute force instead of a
+/// clever search. Only meant for small inputs; see [`aoc_differential!`](crate::aoc_differential).
+pub trait Reference: Solution {
+    fn reference_part_one(parsed: &Self::Parsed<'_>, params: &Params) -> Option<Self::PartOne>;
+    fn reference_part_two(parsed: &Self::Parsed<'_>, params: &Params) -> Option<Self::PartTwo>;
+}
+
+/// An input on which the fast and the reference solver of a part give different answers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Disagreement {
+    pub part: u8,
+    pub fast: Option<String>,
+    pub reference: Option<String>,
+}
+
+impl Display for Disagreement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "part {}: fast solver gave {:?}, reference gave {:?}",
+            self.part, self.fast, self.reference
+        )
+    }
+}
+
+/// Solves both parts of `input` with both solvers and returns every part they disagree on.
+pub fn compare<S: Reference>(input: &str, params: &Params) -> crate::Result<Vec<Disagreement>> {
+    let parsed = S::parse(input)?;
+    let answers = [
+        (
+            S::part_one(&parsed, params).map(|a| a.to_string()),
+            S::reference_part_one(&parsed, params).map(|a| a.to_string()),
+        ),
+        (
+            S::part_two(&parsed, params).map(|a| a.to_string()),
+            S::reference_part_two(&parsed, params).map(|a| a.to_string()),
+        ),
+    ];
+    Ok(answers
+        .into_iter()
+        .zip(1..)
+        .filter(|((fast, reference), _)| fast != reference)
+        .map(|((fast, reference), part)| Disagreement {
+            part,
+            fast,
+            reference,
+        })
+        .collect())
+}
+
+/// Compares both solvers on every `(label, input)` and panics with a report of all
+/// disagreements and unparsable inputs.
+pub fn assert_agree<S: Reference>(
+    inputs: impl IntoIterator<Item = (String, String)>,
+    params: &Params,
+) {
+    let mut report = String::new();
+    for (label, input) in inputs {
+        match compare::<S>(&input, params) {
+            Ok(disagreements) if disagreements.is_empty() => {}
+            Ok(disagreements) => {
+                report += &format!("{label}:\n");
+                for disagreement in disagreements {
+                    report += &format!("  {disagreement}\n");
+                }
+                report += &format!("  input:\n{input}\n");
+            }
+            Err(err) => report += &format!("{label}: {err}\n  input:\n{input}\n"),
+        }
+    }
+    assert!(report.is_empty(), "solvers disagree\n{report}");
+}
+
+/// Generates a `#[cfg(test)] mod differential` comparing a day's fast and [`Reference`]
+/// solvers on its example and on `cases` inputs from a [`generate`](crate::generate) function,
+/// each with a random scale in the `scale` range.
+///
+/// Both run with the example parameters, optionally overridden to keep the reference solvers
+/// fast, e.g. `params = [minutes = 18]`.
+///
+/// ```ignore
+/// aoc_differential!(Day15, day = 15, generate = generate::day15, scale = 1..=8, cases = 100);
+/// ```
+#[macro_export]
+macro_rules! aoc_differential {
+    ($solution:ty, day = $day:expr, generate = $generate:path, scale = $scale:expr, cases = $cases:expr $(, params = [$($name:ident = $value:expr),* $(,)?])? $(,)?) => {
+        #[cfg(test)]
+        mod differential {
+            use super::*;
+
+            fn params() -> $crate::Params {
+                let overrides: &[(String, i64)] = &[$($((stringify!($name).to_string(), $value)),*)?];
+                $crate::Params::new(
+                    <$solution as $crate::Solution>::PARAMS,
+                    $crate::InputKind::Example,
+                )
+                .with_overrides(overrides)
+                .unwrap()
+            }
+
+            #[test]
+            fn example() {
+                let input = $crate::read_file("examples", $day);
+                $crate::differential::assert_agree::<$solution>(
+                    [(String::from("example"), input)],
+                    &params(),
+                );
+            }
+
+            #[test]
+            fn generated() {
+                let inputs = (0..$cases).map(|seed: u64| {
+                    let mut rng = $crate::generate::Rng::new(seed);
+                    let scale = rng.size($scale);
+                    let input = $generate(&mut rng, scale);
+                    (format!("seed {seed}, scale {scale}"), input)
+                });
+                $crate::differential::assert_agree::<$solution>(inputs, &params());
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sums the numbers; the reference is off by one for inputs starting with 0.
+    struct Sum;
+
+    impl Solution for Sum {
+        type Parsed<'a> = Vec<u32>;
+        type PartOne = u32;
+        type PartTwo = u32;
+
+        fn parse(input: &str) -> crate::Result<Vec<u32>> {
+            Ok(input
+                .split_whitespace()
+                .map(|n| n.parse())
+                .collect::<Result<_, _>>()?)
+        }
+
+        fn part_one(numbers: &Vec<u32>, _: &Params) -> Option<u32> {
+            Some(numbers.iter().sum())
+        }
+
+        fn part_two(_: &Vec<u32>, _: &Params) -> Option<u32> {
+            None
+        }
+    }
+
+    impl Reference for Sum {
+        fn reference_part_one(numbers: &Vec<u32>, _: &Params) -> Option<u32> {
+            let sum: u32 = numbers.iter().sum();
+            match numbers.first() {
+                Some(0) => Some(sum + 1),
+                _ => Some(sum),
+            }
+        }
+
+        fn reference_part_two(_: &Vec<u32>, _: &Params) -> Option<u32> {
+            None
+        }
+    }
+
+    #[test]
+    fn agreeing_solvers() {
+        assert_eq!(compare::<Sum>("1 2 3", &Params::default()), Ok(vec![]));
+        assert_agree::<Sum>([("ok".to_string(), "4 5".to_string())], &Params::default());
+    }
+
+    #[test]
+    fn reports_disagreement() {
+        assert_eq!(
+            compare::<Sum>("0 2", &Params::default()),
+            Ok(vec![Disagreement {
+                part: 1,
+                fast: Some("2".to_string()),
+                reference: Some("3".to_string()),
+            }])
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "seed 1:\n  part 1: fast solver gave Some(\"2\"), reference gave Some(\"3\")"
+    )]
+    fn assert_agree_reports_label() {
+        let inputs = [("seed 0", "1 1"), ("seed 1", "0 2")];
+        assert_agree::<Sum>(
+            inputs.map(|(label, input)| (label.to_string(), input.to_string())),
+            &Params::default(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "broken: could not parse input")]
+    fn assert_agree_reports_parse_errors() {
+        assert_agree::<Sum>(
+            [("broken".to_string(), "a".to_string())],
+            &Params::default(),
+        );
+    }
+}
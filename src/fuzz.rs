@@ -0,0 +1,254 @@
+/*
+ * Property tests for a day's parser: round trips through the parsed structure and
+ * mutated inputs that must be rejected without panicking.
+ * Example import: `use advent_of_code::fuzz;`.
+ */
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::generate::Rng;
+use crate::Solution;
+
+/// Characters likely to confuse a parser: digits, signs, separators and line breaks.
+const NOISE: &[char] = &[
+    '0', '1', '9', '-', '+', ',', ':', ';', '=', '[', ']', ' ', '\n', '\t', 'a', 'z', 'A', 'Z',
+    '$', '/', '.', 'é',
+];
+
+/// Numbers at the edges of the usual integer types.
+const EXTREMES: &[&str] = &[
+    "0",
+    "-1",
+    "255",
+    "256",
+    "2147483647",
+    "-2147483648",
+    "4294967295",
+    "4294967296",
+    "99999999999999999999999",
+];
+
+/// Applies one to three random edits to `input`: deleting, inserting or replacing
+/// characters, swapping numbers for extremes, shuffling lines or cutting the input short.
+pub fn mutate(rng: &mut Rng, input: &str) -> String {
+    let mut chars = input.chars().collect::<Vec<_>>();
+    for _ in 0..rng.range(1..=3) {
+        let at = rng.below(chars.len() + 1);
+        match rng.below(20) {
+            0..=3 => {
+                let end = (at + rng.size(1..=3)).min(chars.len());
+                chars.drain(at..end);
+            }
+            4..=7 => chars.insert(at, *rng.pick(NOISE)),
+            8..=11 => match chars.get_mut(at) {
+                Some(c) => *c = *rng.pick(NOISE),
+                None => chars.push(*rng.pick(NOISE)),
+            },
+            12..=14 => {
+                let number = chars[at..]
+                    .iter()
+                    .position(|c| c.is_ascii_digit())
+                    .map(|offset| at + offset);
+                if let Some(start) = number {
+                    let end = chars[start..]
+                        .iter()
+                        .position(|c| !c.is_ascii_digit())
+                        .map_or(chars.len(), |len| start + len);
+                    chars.splice(start..end, rng.pick(EXTREMES).chars());
+                }
+            }
+            15..=17 => {
+                let mut lines = chars.split(|&c| c == '\n').collect::<Vec<_>>();
+                let (a, b) = (rng.below(lines.len()), rng.below(lines.len()));
+                match rng.chance(50) {
+                    true => lines.swap(a, b),
+                    false => lines.insert(a, lines[b]),
+                }
+                chars = lines.join(&'\n');
+            }
+            18 => chars.truncate(at),
+            _ => chars.clear(),
+        }
+    }
+    chars.into_iter().collect()
+}
+
+/// Checks that rendering each parsed `(label, input)` and parsing the rendering again succeeds
+/// and renders the same text, i.e. `render` and [`Solution::parse`] agree on the format.
+pub fn assert_round_trip<S: Solution>(
+    inputs: impl IntoIterator<Item = (String, String)>,
+    render: impl Fn(&S::Parsed<'_>) -> String,
+) {
+    let mut report = String::new();
+    for (label, input) in inputs {
+        let rendered = match S::parse(&input) {
+            Ok(parsed) => render(&parsed),
+            Err(err) => {
+                report += &format!("{label}: {err}\n  input:\n{input}\n");
+                continue;
+            }
+        };
+        match S::parse(&rendered).map(|parsed| render(&parsed)) {
+            Ok(again) if again == rendered => {}
+            Ok(again) => {
+                report += &format!("{label}: rendered\n{rendered}\n  but re-rendered\n{again}\n")
+            }
+            Err(err) => report += &format!("{label}: {err}\n  rendered:\n{rendered}\n"),
+        }
+    }
+    assert!(report.is_empty(), "round trip failed\n{report}");
+}
+
+/// Parses every `(label, input)` and panics with a report of every input whose parser
+/// panicked instead of returning an error.
+pub fn assert_no_panics<S: Solution>(inputs: impl IntoIterator<Item = (String, String)>) {
+    let mut report = String::new();
+    for (label, input) in inputs {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let _ = S::parse(&input);
+        }));
+        if let Err(err) = result {
+            let message = err
+                .downcast_ref::<String>()
+                .map(String::as_str)
+                .or_else(|| err.downcast_ref::<&str>().copied())
+                .unwrap_or("unknown panic");
+            report += &format!("{label}: {message}\n  input:\n{input:?}\n");
+        }
+    }
+    assert!(report.is_empty(), "parser panicked\n{report}");
+}
+
+/// Generates a `#[cfg(test)] mod fuzz` with property tests for a day's parser, on its example
+/// and on `cases` inputs from a [`generate`](crate::generate) function:
+///
+/// - `round_trip`: `render` turns the parsed input back into text that parses the same way.
+/// - `mutations`: `mutations` random edits of each input never make the parser panic.
+///
+/// ```ignore
+/// aoc_fuzz!(Day09, day = 9, generate = generate::day09, scale = 1..=50, cases = 50,
+///     render = render_moves, mutations = 20);
+/// ```
+#[macro_export]
+macro_rules! aoc_fuzz {
+    ($solution:ty, day = $day:expr, generate = $generate:path, scale = $scale:expr, cases = $cases:expr, render = $render:path, mutations = $mutations:expr $(,)?) => {
+        #[cfg(test)]
+        mod fuzz {
+            use super::*;
+
+            fn inputs() -> impl Iterator<Item = (String, String)> {
+                let example = (String::from("example"), $crate::read_file("examples", $day));
+                let generated = (0..$cases).map(|seed: u64| {
+                    let mut rng = $crate::generate::Rng::new(seed);
+                    let scale = rng.size($scale);
+                    (
+                        format!("seed {seed}, scale {scale}"),
+                        $generate(&mut rng, scale),
+                    )
+                });
+                std::iter::once(example).chain(generated)
+            }
+
+            #[test]
+            fn round_trip() {
+                $crate::fuzz::assert_round_trip::<$solution>(inputs(), |parsed| $render(parsed));
+            }
+
+            #[test]
+            fn mutations() {
+                let mut rng = $crate::generate::Rng::new($day);
+                let mutated = inputs().flat_map(|(label, input)| {
+                    (0..$mutations)
+                        .map(|i| {
+                            (
+                                format!("{label}, mutation {i}"),
+                                $crate::fuzz::mutate(&mut rng, &input),
+                            )
+                        })
+                        .collect::<Vec<_>>()
+                });
+                $crate::fuzz::assert_no_panics::<$solution>(mutated);
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Params;
+
+    /// Comma separated numbers; panics on an empty entry.
+    struct Numbers;
+
+    impl Solution for Numbers {
+        type Parsed<'a> = Vec<u8>;
+        type PartOne = u8;
+        type PartTwo = u8;
+
+        fn parse(input: &str) -> crate::Result<Vec<u8>> {
+            input
+                .split(',')
+                .map(|n| {
+                    assert!(!n.is_empty(), "empty entry");
+                    Ok(n.parse()?)
+                })
+                .collect()
+        }
+
+        fn part_one(_: &Vec<u8>, _: &Params) -> Option<u8> {
+            None
+        }
+
+        fn part_two(_: &Vec<u8>, _: &Params) -> Option<u8> {
+            None
+        }
+    }
+
+    fn render(numbers: &[u8]) -> String {
+        numbers
+            .iter()
+            .map(u8::to_string)
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    fn labelled(inputs: &[&str]) -> Vec<(String, String)> {
+        inputs
+            .iter()
+            .map(|input| (format!("{input:?}"), input.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn mutations_are_deterministic() {
+        let input = "1,2,3\n4,5,6";
+        let mutated = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..20).map(|_| mutate(&mut rng, input)).collect::<Vec<_>>()
+        };
+        assert_eq!(mutated(1), mutated(1));
+        assert!(mutated(1).iter().any(|m| m != input));
+    }
+
+    #[test]
+    fn round_trip() {
+        assert_round_trip::<Numbers>(labelled(&["1,2,3", "007"]), |n| render(n));
+    }
+
+    #[test]
+    #[should_panic(expected = "\"1,x\": could not parse input")]
+    fn round_trip_reports_unparsable_inputs() {
+        assert_round_trip::<Numbers>(labelled(&["1,x"]), |n| render(n));
+    }
+
+    #[test]
+    fn errors_are_not_panics() {
+        assert_no_panics::<Numbers>(labelled(&["1,x", "300"]));
+    }
+
+    #[test]
+    #[should_panic(expected = "\"1,,2\": empty entry")]
+    fn reports_panics() {
+        assert_no_panics::<Numbers>(labelled(&["1,2", "1,,2"]));
+    }
+}
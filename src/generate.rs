@@ -0,0 +1,676 @@
+/*
+ * Seeded generators for random puzzle inputs, for differential tests, stress tests and
+ * benchmarks. `scale` sets the number of items in the input, e.g. moves or blueprints.
+ * Example import: `use advent_of_code::generate::{self, Rng};`.
+ */
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::ops::RangeInclusive;
+
+/// A day's input generator.
+pub struct Generator {
+    pub day: u8,
+    pub generate: fn(&mut Rng, usize) -> String,
+    /// The scale of a real puzzle input.
+    pub real_scale: usize,
+    /// What `scale` counts.
+    pub unit: &'static str,
+}
+
+pub const GENERATORS: &[Generator] = &[
+    Generator {
+        day: 1,
+        generate: day01,
+        real_scale: 250,
+        unit: "elves",
+    },
+    Generator {
+        day: 2,
+        generate: day02,
+        real_scale: 2500,
+        unit: "rounds",
+    },
+    Generator {
+        day: 3,
+        generate: day03,
+        real_scale: 300,
+        unit: "rucksacks",
+    },
+    Generator {
+        day: 4,
+        generate: day04,
+        real_scale: 1000,
+        unit: "pairs",
+    },
+    Generator {
+        day: 5,
+        generate: day05,
+        real_scale: 500,
+        unit: "moves",
+    },
+    Generator {
+        day: 6,
+        generate: day06,
+        real_scale: 4096,
+        unit: "characters",
+    },
+    Generator {
+        day: 7,
+        generate: day07,
+        real_scale: 180,
+        unit: "directories",
+    },
+    Generator {
+        day: 8,
+        generate: day08,
+        real_scale: 99,
+        unit: "rows",
+    },
+    Generator {
+        day: 9,
+        generate: day09,
+        real_scale: 2000,
+        unit: "moves",
+    },
+    Generator {
+        day: 10,
+        generate: day10,
+        real_scale: 140,
+        unit: "instructions",
+    },
+    Generator {
+        day: 11,
+        generate: day11,
+        real_scale: 8,
+        unit: "monkeys",
+    },
+    Generator {
+        day: 12,
+        generate: day12,
+        real_scale: 41,
+        unit: "rows",
+    },
+    Generator {
+        day: 13,
+        generate: day13,
+        real_scale: 150,
+        unit: "packet pairs",
+    },
+    Generator {
+        day: 14,
+        generate: day14,
+        real_scale: 150,
+        unit: "rock paths",
+    },
+    Generator {
+        day: 15,
+        generate: day15,
+        real_scale: 30,
+        unit: "sensors",
+    },
+    Generator {
+        day: 16,
+        generate: day16,
+        real_scale: 60,
+        unit: "valves",
+    },
+    Generator {
+        day: 18,
+        generate: day18,
+        real_scale: 2000,
+        unit: "cubes",
+    },
+    Generator {
+        day: 19,
+        generate: day19,
+        real_scale: 30,
+        unit: "blueprints",
+    },
+];
+
+pub fn generator(day: u8) -> Option<&'static Generator> {
+    GENERATORS.iter().find(|g| g.day == day)
+}
+
+/// A small deterministic random number generator (SplitMix64).
+/// The same seed always produces the same inputs, so failures can be reproduced.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A uniformly distributed value in `range`.
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let span = range.start().abs_diff(*range.end()) + 1;
+        range.start().wrapping_add((self.next_u64() % span) as i64)
+    }
+
+    /// A uniformly distributed size in `range`.
+    pub fn size(&mut self, range: RangeInclusive<usize>) -> usize {
+        range.start() + self.below(range.end() - range.start() + 1)
+    }
+
+    /// An index in `0..len`. Panics if `len` is 0.
+    pub fn below(&mut self, len: usize) -> usize {
+        assert!(len > 0, "cannot pick from an empty range");
+        (self.next_u64() % len as u64) as usize
+    }
+
+    /// `true` with probability `percent` / 100.
+    pub fn chance(&mut self, percent: u64) -> bool {
+        self.next_u64() % 100 < percent
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    /// Puts `items` in a uniformly random order.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// `scale` elves carrying up to 15 snacks each.
+pub fn day01(rng: &mut Rng, scale: usize) -> String {
+    (0..scale)
+        .map(|_| {
+            (0..rng.range(1..=15))
+                .map(|_| rng.range(1000..=60_000).to_string())
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// `scale` rounds of rock paper scissors.
+pub fn day02(rng: &mut Rng, scale: usize) -> String {
+    (0..scale)
+        .map(|_| {
+            format!(
+                "{} {}",
+                rng.pick(&["A", "B", "C"]),
+                rng.pick(&["X", "Y", "Z"])
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// `scale` rucksacks. Both compartments of a rucksack share one item type, and each group
+/// of three rucksacks carries a common badge in its first compartments.
+pub fn day03(rng: &mut Rng, scale: usize) -> String {
+    let letters = ('a'..='z').chain('A'..='Z').collect::<Vec<_>>();
+    let mut badge = letters[0];
+    let mut rucksacks = Vec::new();
+    for rucksack in 0..scale {
+        if rucksack % 3 == 0 {
+            badge = *rng.pick(&letters);
+        }
+        let mut others = letters
+            .iter()
+            .copied()
+            .filter(|&c| c != badge)
+            .collect::<Vec<_>>();
+        rng.shuffle(&mut others);
+        // the shared item, then the items only found in one compartment or the other
+        let shared = others[0];
+        let (first, second) = others[1..].split_at(others.len() / 2);
+        let size = rng.size(4..=16);
+        let mut compartments = [vec![badge, shared], vec![shared]];
+        for (compartment, items) in compartments.iter_mut().zip([first, second]) {
+            while compartment.len() < size {
+                compartment.push(*rng.pick(items));
+            }
+            rng.shuffle(compartment);
+        }
+        rucksacks.push(compartments.concat().into_iter().collect::<String>());
+    }
+    rucksacks.join("\n")
+}
+
+/// `scale` pairs of section assignments within sections 1 to 99.
+pub fn day04(rng: &mut Rng, scale: usize) -> String {
+    let mut assignment = || {
+        let start = rng.range(1..=99);
+        format!("{start}-{}", rng.range(start..=99))
+    };
+    (0..scale)
+        .map(|_| format!("{},{}", assignment(), assignment()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Nine stacks of crates and a procedure of `scale` moves, each taking crates that are there.
+pub fn day05(rng: &mut Rng, scale: usize) -> String {
+    let mut heights = (0..9).map(|_| rng.size(1..=8)).collect::<Vec<_>>();
+    let mut lines = (0..*heights.iter().max().unwrap())
+        .rev()
+        .map(|level| {
+            heights
+                .iter()
+                .map(|&height| match level < height {
+                    true => format!("[{}]", (b'A' + rng.below(26) as u8) as char),
+                    false => "   ".to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>();
+    lines.push(
+        (1..=9)
+            .map(|i| format!(" {i} "))
+            .collect::<Vec<_>>()
+            .join(" "),
+    );
+    lines.push(String::new());
+
+    for _ in 0..scale {
+        let stacked = (0..9).filter(|&i| heights[i] > 0).collect::<Vec<_>>();
+        let from = *rng.pick(&stacked);
+        let to = (from + rng.size(1..=8)) % 9;
+        let count = rng.size(1..=heights[from].min(10));
+        heights[from] -= count;
+        heights[to] += count;
+        lines.push(format!("move {count} from {} to {}", from + 1, to + 1));
+    }
+    lines.join("\n")
+}
+
+/// A datastream of `scale` characters (at least 15), with a start-of-message marker in its
+/// second half at the latest.
+pub fn day06(rng: &mut Rng, scale: usize) -> String {
+    let len = scale.max(15);
+    let mut stream = (0..len)
+        .map(|_| (b'a' + rng.below(26) as u8) as char)
+        .collect::<Vec<_>>();
+    let mut marker = ('a'..='z').collect::<Vec<_>>();
+    rng.shuffle(&mut marker);
+    // the last character isn't searched, so the marker ends before it
+    let at = rng.size((len / 2).min(len - 15)..=len - 15);
+    stream[at..at + 14].copy_from_slice(&marker[..14]);
+    stream.into_iter().collect()
+}
+
+/// A filesystem of `scale` directories, explored depth first from `$ cd /`.
+/// Directories hold up to 3 files of at most 300000 bytes each, so sizes fit a `u32`
+/// below about 5000 directories.
+pub fn day07(rng: &mut Rng, scale: usize) -> String {
+    /// `a`, `b`, ..., `z`, `ba`, `bb`, ...
+    fn name(mut index: usize) -> String {
+        let mut name = vec![];
+        loop {
+            name.push((b'a' + (index % 26) as u8) as char);
+            index /= 26;
+            if index == 0 {
+                return name.into_iter().rev().collect();
+            }
+        }
+    }
+
+    fn explore(rng: &mut Rng, dir: usize, children: &[Vec<usize>], lines: &mut Vec<String>) {
+        lines.push("$ ls".to_string());
+        let files = match (rng.below(4), children[dir].is_empty()) {
+            (0, true) => 1,
+            (files, _) => files,
+        };
+        for i in 0..children[dir].len() {
+            lines.push(format!("dir {}", name(i)));
+        }
+        for i in 0..files {
+            let extension = rng.pick(&["", ".txt", ".dat", ".log"]);
+            lines.push(format!("{} {}{extension}", rng.range(1..=300_000), name(i)));
+        }
+        for (i, &child) in children[dir].iter().enumerate() {
+            lines.push(format!("$ cd {}", name(i)));
+            explore(rng, child, children, lines);
+            lines.push("$ cd ..".to_string());
+        }
+    }
+
+    // Directory 0 is the root, every other one goes into an earlier one.
+    let mut children = vec![Vec::new(); scale + 1];
+    for dir in 1..=scale {
+        children[rng.below(dir)].push(dir);
+    }
+    let mut lines = vec!["$ cd /".to_string()];
+    explore(rng, 0, &children, &mut lines);
+    lines.join("\n")
+}
+
+/// A forest of `scale` by `scale` trees.
+pub fn day08(rng: &mut Rng, scale: usize) -> String {
+    (0..scale)
+        .map(|_| {
+            (0..scale)
+                .map(|_| char::from_digit(rng.below(10) as u32, 10).unwrap())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// `scale` rope moves of up to 19 steps.
+pub fn day09(rng: &mut Rng, scale: usize) -> String {
+    (0..scale)
+        .map(|_| format!("{} {}", rng.pick(&["U", "D", "L", "R"]), rng.range(1..=19)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// `scale` CPU instructions.
+pub fn day10(rng: &mut Rng, scale: usize) -> String {
+    (0..scale)
+        .map(|_| match rng.chance(40) {
+            true => "noop".to_string(),
+            false => format!("addx {}", rng.range(-20..=20)),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// `scale` monkeys (at least 2), each throwing to two others.
+pub fn day11(rng: &mut Rng, scale: usize) -> String {
+    const PRIMES: [i64; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+    let count = scale.max(2);
+    let mut monkeys = Vec::new();
+    for id in 0..count {
+        let items = (0..rng.range(1..=5))
+            .map(|_| rng.range(50..=99).to_string())
+            .collect::<Vec<_>>();
+        let operation = match rng.below(3) {
+            0 => "old * old".to_string(),
+            1 => format!("old * {}", rng.range(2..=19)),
+            _ => format!("old + {}", rng.range(1..=8)),
+        };
+        let mut target = || {
+            let other = rng.below(count - 1);
+            other + usize::from(other >= id)
+        };
+        let (if_true, if_false) = (target(), target());
+        monkeys.push(format!(
+            "Monkey {id}:\n  Starting items: {}\n  Operation: new = {operation}\n  \
+             Test: divisible by {}\n    If true: throw to monkey {if_true}\n    \
+             If false: throw to monkey {if_false}",
+            items.join(", "),
+            PRIMES[id % PRIMES.len()],
+        ));
+    }
+    monkeys.join("\n\n")
+}
+
+/// A heightmap of `scale` rows, four times as wide (at least 30), with the start on the left
+/// edge and the best signal near the right one. Neighbouring squares differ by at most one
+/// in height, so every square can reach every other one.
+pub fn day12(rng: &mut Rng, scale: usize) -> String {
+    let (height, width) = (scale.max(1), (scale * 4).max(30));
+    let start = (0, rng.below(height));
+    let end = (rng.size(width - 5..=width - 1), rng.below(height));
+    let hills = (0..rng.size(2..=6))
+        .map(|_| ((rng.below(width), rng.below(height)), rng.size(3..=20)))
+        .collect::<Vec<_>>();
+    let distance =
+        |(x, y): (usize, usize), (to_x, to_y): (usize, usize)| x.abs_diff(to_x) + y.abs_diff(to_y);
+
+    (0..height)
+        .map(|y| {
+            (0..width)
+                .map(|x| {
+                    let at = (x, y);
+                    // each cone changes by at most one per step, and so do their max and min
+                    let peak = hills
+                        .iter()
+                        .map(|&(hill, top)| top.saturating_sub(distance(at, hill)))
+                        .fold(25usize.saturating_sub(distance(at, end) / 2), usize::max)
+                        .min(distance(at, start));
+                    match at {
+                        _ if at == start => 'S',
+                        _ if at == end => 'E',
+                        _ => (b'a' + peak as u8) as char,
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// `scale` pairs of packets, nested up to 4 lists deep.
+pub fn day13(rng: &mut Rng, scale: usize) -> String {
+    fn packet(rng: &mut Rng, depth: u32) -> String {
+        let items = (0..rng.range(0..=4))
+            .map(|_| match depth < 4 && rng.chance(30) {
+                true => packet(rng, depth + 1),
+                false => rng.range(0..=10).to_string(),
+            })
+            .collect::<Vec<_>>();
+        format!("[{}]", items.join(","))
+    }
+
+    (0..scale)
+        .map(|_| format!("{}\n{}", packet(rng, 0), packet(rng, 0)))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// `scale` rock paths below the sand source at `500,0`, each alternating between
+/// horizontal and vertical segments.
+pub fn day14(rng: &mut Rng, scale: usize) -> String {
+    let mut paths = Vec::new();
+    for _ in 0..scale {
+        let (mut x, mut y) = (rng.range(450..=550), rng.range(5..=170));
+        let mut points = vec![format!("{x},{y}")];
+        let mut horizontal = rng.chance(50);
+        for _ in 0..rng.range(1..=4) {
+            let step = rng.range(1..=8);
+            let (at, min, max) = match horizontal {
+                true => (&mut x, 400, 600),
+                false => (&mut y, 1, 175),
+            };
+            *at = match *at + step <= max && (rng.chance(50) || *at - step < min) {
+                true => *at + step,
+                false => *at - step,
+            };
+            points.push(format!("{x},{y}"));
+            horizontal = !horizontal;
+        }
+        paths.push(points.join(" -> "));
+    }
+    paths.join("\n")
+}
+
+/// `scale` sensors and their beacons around the example's search area (`row = 10`,
+/// `bound = 20`).
+pub fn day15(rng: &mut Rng, scale: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..scale {
+        let (x, y) = (rng.range(-2..=22), rng.range(-2..=22));
+        let (beacon_x, beacon_y) = (x + rng.range(-6..=6), y + rng.range(-6..=6));
+        writeln!(
+            input,
+            "Sensor at x={x}, y={y}: closest beacon is at x={beacon_x}, y={beacon_y}"
+        )
+        .unwrap();
+    }
+    input
+}
+
+/// A connected tunnel network of `scale` valves (at most 676), starting at `AA`.
+/// Like in real inputs, at most 15 valves have a positive flow rate.
+pub fn day16(rng: &mut Rng, scale: usize) -> String {
+    assert!(scale <= 26 * 26, "valve names only have two letters");
+    let count = scale.max(1);
+    let mut names = vec!["AA".to_string()];
+    let mut seen = HashSet::from(["AA".to_string()]);
+    while names.len() < count {
+        let name = (0..2)
+            .map(|_| (b'A' + rng.below(26) as u8) as char)
+            .collect::<String>();
+        if seen.insert(name.clone()) {
+            names.push(name);
+        }
+    }
+
+    let mut tunnels = vec![Vec::new(); count];
+    let mut edges = (1..count)
+        .map(|valve| (valve, rng.below(valve)))
+        .collect::<Vec<_>>();
+    for _ in 0..rng.below(count) {
+        edges.push((rng.below(count), rng.below(count)));
+    }
+    for (a, b) in edges {
+        if a != b && !tunnels[a].contains(&b) {
+            tunnels[a].push(b);
+            tunnels[b].push(a);
+        }
+    }
+
+    let mut input = String::new();
+    let mut flowing = 0;
+    for (valve, name) in names.iter().enumerate() {
+        let rate = match valve {
+            0 => 0,
+            _ if flowing == 15 || rng.chance(30) => 0,
+            _ => {
+                flowing += 1;
+                rng.range(1..=25)
+            }
+        };
+        let to = tunnels[valve]
+            .iter()
+            .map(|&t| names[t].as_str())
+            .collect::<Vec<_>>();
+        let (tunnels, valves) = match to.len() {
+            1 => ("tunnel leads", "valve"),
+            _ => ("tunnels lead", "valves"),
+        };
+        writeln!(
+            input,
+            "Valve {name} has flow rate={rate}; {tunnels} to {valves} {}",
+            to.join(", ")
+        )
+        .unwrap();
+    }
+    input
+}
+
+/// `scale` distinct cubes (at most 8000) within a 20x20x20 box.
+pub fn day18(rng: &mut Rng, scale: usize) -> String {
+    assert!(scale <= 20 * 20 * 20, "the box only fits 8000 cubes");
+    let mut cubes = HashSet::new();
+    let mut input = Vec::new();
+    while input.len() < scale {
+        let cube = (rng.range(1..=20), rng.range(1..=20), rng.range(1..=20));
+        if cubes.insert(cube) {
+            input.push(format!("{},{},{}", cube.0, cube.1, cube.2));
+        }
+    }
+    input.join("\n")
+}
+
+/// `scale` blueprints. Robots are cheaper than in real inputs, so some geodes get cracked
+/// even in short games.
+pub fn day19(rng: &mut Rng, scale: usize) -> String {
+    let mut input = String::new();
+    for id in 1..=scale {
+        writeln!(
+            input,
+            "Blueprint {id}: Each ore robot costs {} ore. Each clay robot costs {} ore. \
+             Each obsidian robot costs {} ore and {} clay. Each geode robot costs {} ore and {} obsidian.",
+            rng.range(1..=4),
+            rng.range(1..=4),
+            rng.range(1..=4),
+            rng.range(1..=8),
+            rng.range(1..=4),
+            rng.range(1..=8),
+        )
+        .unwrap();
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_input() {
+        assert_eq!(day16(&mut Rng::new(7), 9), day16(&mut Rng::new(7), 9));
+        assert_ne!(day19(&mut Rng::new(1), 3), day19(&mut Rng::new(2), 3));
+    }
+
+    #[test]
+    fn scale_counts_items() {
+        for generator in GENERATORS {
+            let input = (generator.generate)(&mut Rng::new(0), 12);
+            let lines = input.lines().count();
+            match generator.day {
+                1 => assert_eq!(input.split("\n\n").count(), 12),
+                5 => assert_eq!(input.split_once("\n\n").unwrap().1.lines().count(), 12),
+                // too short for a start-of-message marker
+                6 => assert_eq!(input.len(), 15),
+                7 => assert_eq!(input.matches("$ cd ..").count(), 12),
+                11 => assert_eq!(input.matches("Monkey").count(), 12),
+                13 => assert_eq!(lines, 12 * 3 - 1),
+                _ => assert_eq!(lines, 12, "day {}", generator.day),
+            }
+        }
+    }
+
+    #[test]
+    fn cd_only_into_listed_directories() {
+        let input = day07(&mut Rng::new(3), 50);
+        let mut listed = vec![HashSet::new()];
+        for line in input.lines().skip(1) {
+            if line == "$ ls" {
+                listed.last_mut().unwrap().clear();
+            } else if let Some(dir) = line.strip_prefix("dir ") {
+                listed.last_mut().unwrap().insert(dir.to_string());
+            } else if line == "$ cd .." {
+                listed.pop();
+            } else if let Some(dir) = line.strip_prefix("$ cd ") {
+                assert!(listed.last().unwrap().contains(dir), "{dir} was not listed");
+                listed.push(HashSet::new());
+            }
+        }
+    }
+
+    #[test]
+    fn rock_paths_are_straight() {
+        for path in day14(&mut Rng::new(5), 100).lines() {
+            let points = path
+                .split(" -> ")
+                .map(|p| p.split_once(',').unwrap())
+                .collect::<Vec<_>>();
+            for pair in points.windows(2) {
+                assert!(pair[0].0 == pair[1].0 || pair[0].1 == pair[1].1, "{path}");
+                assert_ne!(pair[0], pair[1], "{path}");
+            }
+        }
+    }
+
+    #[test]
+    fn range_is_inclusive() {
+        let mut rng = Rng::new(0);
+        let values = (0..1000).map(|_| rng.range(-1..=1)).collect::<HashSet<_>>();
+        assert_eq!(values, HashSet::from([-1, 0, 1]));
+    }
+}
@@ -2,3 +2,11 @@
  * Use this file if you want to extract helpers from your solutions.
  * Example import from this file: `use advent_of_code::helpers::example_fn;`.
  */
+
+pub mod cycle;
+pub mod interval;
+pub mod math;
+pub mod optimize;
+pub mod stepper;
+pub mod viz;
+pub mod voxel;
@@ -0,0 +1,195 @@
+/*
+ * Cycle detection for step-by-step simulations, to fast-forward to far away steps.
+ * Example import: `use advent_of_code::helpers::cycle::{simulate, fast_forward};`.
+ */
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The simulation repeats from step `start` on, every `length` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The earliest step that is in the same state as `step`.
+    pub fn reduce(&self, step: usize) -> usize {
+        if step < self.start {
+            step
+        } else {
+            self.start + (step - self.start) % self.length
+        }
+    }
+}
+
+/// States recorded by [`simulate`], along with the cycle if one was found.
+#[derive(Debug, Clone)]
+pub struct Simulation<S> {
+    history: Vec<S>,
+    cycle: Option<Cycle>,
+}
+
+impl<S> Simulation<S> {
+    pub fn cycle(&self) -> Option<Cycle> {
+        self.cycle
+    }
+
+    /// All simulated states, starting with the initial one.
+    pub fn history(&self) -> &[S] {
+        &self.history
+    }
+
+    /// The state after `step` steps, or `None` if it is past the simulated steps and no
+    /// cycle was found.
+    pub fn state_at(&self, step: usize) -> Option<&S> {
+        match self.cycle {
+            Some(cycle) => self.history.get(cycle.reduce(step)),
+            None => self.history.get(step),
+        }
+    }
+
+    /// Extrapolates a metric that may keep growing while the state cycles, e.g. the height of
+    /// a tower of rocks. The metric must increase by the same amount in every cycle.
+    pub fn extrapolate(&self, step: usize, metric: impl Fn(&S) -> i64) -> Option<i64> {
+        if let Some(state) = self.history.get(step) {
+            return Some(metric(state));
+        }
+        let cycle = self.cycle?;
+        let per_cycle =
+            metric(&self.history[cycle.start + cycle.length]) - metric(&self.history[cycle.start]);
+        let cycles = ((step - cycle.start) / cycle.length) as i64;
+        Some(metric(&self.history[cycle.reduce(step)]) + cycles * per_cycle)
+    }
+}
+
+/// Runs `step` until a state repeats, i.e. two states have the same `key`, or until
+/// `max_steps` steps have been simulated.
+///
+/// The key should leave out anything that grows forever (counters, heights) so that
+/// repeating states can be detected; use [`Simulation::extrapolate`] for those values.
+pub fn simulate<S, K: Hash + Eq>(
+    initial: S,
+    max_steps: usize,
+    mut step: impl FnMut(&S) -> S,
+    mut key: impl FnMut(&S) -> K,
+) -> Simulation<S> {
+    let mut seen = HashMap::from([(key(&initial), 0)]);
+    let mut history = vec![initial];
+    for i in 1..=max_steps {
+        let next = step(&history[i - 1]);
+        let next_key = key(&next);
+        history.push(next);
+        if let Some(&start) = seen.get(&next_key) {
+            return Simulation {
+                history,
+                cycle: Some(Cycle {
+                    start,
+                    length: i - start,
+                }),
+            };
+        }
+        seen.insert(next_key, i);
+    }
+    Simulation {
+        history,
+        cycle: None,
+    }
+}
+
+/// The state after `target` steps, skipping over repetitions once a cycle is found.
+/// Without a cycle, all `target` steps run and every state is kept in memory.
+pub fn fast_forward<S: Clone, K: Hash + Eq>(
+    initial: S,
+    target: usize,
+    step: impl FnMut(&S) -> S,
+    key: impl FnMut(&S) -> K,
+) -> S {
+    simulate(initial, target, step, key)
+        .state_at(target)
+        .cloned()
+        .expect("simulation either cycles or reaches the target")
+}
+
+/// Brent's algorithm: finds the cycle of a sequence in constant memory. The sequence
+/// must eventually repeat, or this never terminates.
+pub fn brent<S: Clone + PartialEq>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    Cycle { start, length }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn next(x: &u32) -> u32 {
+        (x * x + 1) % 255
+    }
+
+    #[test]
+    fn test_simulate() {
+        let sim = simulate(3, 1000, next, |&x| x);
+        let cycle = sim.cycle().unwrap();
+        assert_eq!(cycle, brent(3, next));
+
+        let mut naive = 3;
+        for step in 0..500 {
+            assert_eq!(sim.state_at(step), Some(&naive));
+            naive = next(&naive);
+        }
+        assert_eq!(
+            fast_forward(3, 499, next, |&x| x),
+            sim.state_at(499).copied().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_without_cycle() {
+        let sim = simulate(0_u64, 10, |x| x + 1, |&x| x);
+        assert_eq!(sim.cycle(), None);
+        assert_eq!(sim.state_at(10), Some(&10));
+        assert_eq!(sim.state_at(11), None);
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        // (position in a pattern of 7, accumulated height)
+        let heights = [1, 3, 0, 2, 4, 1, 1];
+        let step = |&(i, h): &(usize, i64)| ((i + 1) % 7, h + heights[i]);
+        let sim = simulate((0, 0), 100, step, |&(i, _)| i);
+
+        let mut naive = (0, 0);
+        for _ in 0..1000 {
+            naive = step(&naive);
+        }
+        assert_eq!(sim.extrapolate(1000, |&(_, h)| h), Some(naive.1));
+        assert_eq!(
+            sim.extrapolate(1_000_000_000_000, |&(_, h)| h),
+            Some(1_000_000_000_000 / 7 * 12 + heights[0])
+        );
+    }
+}
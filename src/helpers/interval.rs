@@ -0,0 +1,298 @@
+/*
+ * Inclusive integer intervals and sets of them, for 1D coverage problems.
+ * Example import: `use advent_of_code::helpers::interval::{Interval, IntervalSet};`.
+ */
+use std::fmt::Display;
+
+/// An inclusive range of integers `start..=end`. Never empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Interval {
+    /// Returns `None` if `end < start`.
+    pub fn new(start: i64, end: i64) -> Option<Self> {
+        if end < start {
+            None
+        } else {
+            Some(Self { start, end })
+        }
+    }
+
+    pub fn point(at: i64) -> Self {
+        Self { start: at, end: at }
+    }
+
+    /// Number of integers in the interval.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> u64 {
+        self.start.abs_diff(self.end) + 1
+    }
+
+    pub fn contains(&self, at: i64) -> bool {
+        self.start <= at && at <= self.end
+    }
+
+    pub fn contains_interval(&self, other: &Interval) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        Interval::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// Overlapping or directly adjacent, i.e. the union is a single interval.
+    fn touches(&self, other: &Interval) -> bool {
+        self.start <= other.end.saturating_add(1) && other.start <= self.end.saturating_add(1)
+    }
+}
+
+impl Display for Interval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}..={}", self.start, self.end)
+    }
+}
+
+/// A set of integers stored as sorted, disjoint and non-adjacent intervals.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Total number of integers in the set.
+    pub fn len(&self) -> u64 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Interval> + '_ {
+        self.intervals.iter().copied()
+    }
+
+    pub fn contains(&self, at: i64) -> bool {
+        let i = self.intervals.partition_point(|i| i.end < at);
+        self.intervals.get(i).is_some_and(|i| i.contains(at))
+    }
+
+    pub fn insert(&mut self, interval: Interval) {
+        let first = self
+            .intervals
+            .partition_point(|i| i.end.saturating_add(1) < interval.start);
+        let mut merged = interval;
+        let mut last = first;
+        while let Some(i) = self.intervals.get(last) {
+            if !i.touches(&merged) {
+                break;
+            }
+            merged = Interval {
+                start: merged.start.min(i.start),
+                end: merged.end.max(i.end),
+            };
+            last += 1;
+        }
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    pub fn remove(&mut self, interval: Interval) {
+        let mut kept = Vec::with_capacity(self.intervals.len() + 1);
+        for i in self.intervals.drain(..) {
+            if !i.overlaps(&interval) {
+                kept.push(i);
+                continue;
+            }
+            if i.start < interval.start {
+                kept.push(Interval {
+                    start: i.start,
+                    end: interval.start - 1,
+                });
+            }
+            if interval.end < i.end {
+                kept.push(Interval {
+                    start: interval.end + 1,
+                    end: i.end,
+                });
+            }
+        }
+        self.intervals = kept;
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        let mut set = self.clone();
+        set.extend(other.iter());
+        set
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut intervals = Vec::new();
+        let (mut a, mut b) = (0, 0);
+        while let (Some(left), Some(right)) = (self.intervals.get(a), other.intervals.get(b)) {
+            if let Some(i) = left.intersection(right) {
+                intervals.push(i);
+            }
+            if left.end < right.end {
+                a += 1;
+            } else {
+                b += 1;
+            }
+        }
+        IntervalSet { intervals }
+    }
+
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut set = self.clone();
+        for interval in other.iter() {
+            set.remove(interval);
+        }
+        set
+    }
+
+    /// The set clipped to `bounds`.
+    pub fn clamp(&self, bounds: Interval) -> IntervalSet {
+        self.intersection(&IntervalSet::from(bounds))
+    }
+
+    /// Maximal intervals inside `bounds` that are not part of the set.
+    pub fn gaps(&self, bounds: Interval) -> impl Iterator<Item = Interval> + '_ {
+        let mut next_start = Some(bounds.start);
+        self.intervals
+            .iter()
+            .map(|i| Some(*i))
+            .chain([None])
+            .filter_map(move |interval| {
+                let start = next_start?;
+                let end = match interval {
+                    Some(i) => {
+                        if i.end < start {
+                            return None;
+                        }
+                        next_start = i.end.checked_add(1).filter(|&s| s <= bounds.end);
+                        i.start.checked_sub(1)?
+                    }
+                    None => bounds.end,
+                };
+                Interval::new(start, end.min(bounds.end))
+            })
+    }
+}
+
+impl From<Interval> for IntervalSet {
+    fn from(interval: Interval) -> Self {
+        Self {
+            intervals: vec![interval],
+        }
+    }
+}
+
+impl Extend<Interval> for IntervalSet {
+    fn extend<T: IntoIterator<Item = Interval>>(&mut self, iter: T) {
+        for interval in iter {
+            self.insert(interval);
+        }
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<T: IntoIterator<Item = Interval>>(iter: T) -> Self {
+        let mut intervals: Vec<Interval> = iter.into_iter().collect();
+        intervals.sort_unstable();
+        let mut set = IntervalSet::new();
+        for interval in intervals {
+            match set.intervals.last_mut() {
+                Some(last) if last.touches(&interval) => last.end = last.end.max(interval.end),
+                _ => set.intervals.push(interval),
+            }
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn i(start: i64, end: i64) -> Interval {
+        Interval::new(start, end).unwrap()
+    }
+
+    #[test]
+    fn test_interval() {
+        assert_eq!(Interval::new(3, 2), None);
+        assert_eq!(i(2, 4).len(), 3);
+        assert!(i(2, 8).contains_interval(&i(3, 7)));
+        assert!(!i(3, 7).contains_interval(&i(2, 8)));
+        assert!(i(2, 4).overlaps(&i(4, 6)));
+        assert!(!i(2, 4).overlaps(&i(5, 6)));
+        assert_eq!(i(2, 6).intersection(&i(4, 8)), Some(i(4, 6)));
+    }
+
+    #[test]
+    fn test_insert_merges() {
+        let mut set = IntervalSet::new();
+        set.insert(i(10, 12));
+        set.insert(i(1, 3));
+        set.insert(i(5, 6));
+        assert_eq!(set.intervals(), &[i(1, 3), i(5, 6), i(10, 12)]);
+        set.insert(i(4, 4));
+        assert_eq!(set.intervals(), &[i(1, 6), i(10, 12)]);
+        set.insert(i(0, 20));
+        assert_eq!(set.intervals(), &[i(0, 20)]);
+        assert_eq!(set.len(), 21);
+    }
+
+    #[test]
+    fn test_from_iter_matches_insert() {
+        let intervals = [i(8, 9), i(-2, 2), i(3, 3), i(12, 15), i(14, 20), i(5, 6)];
+        let collected: IntervalSet = intervals.into_iter().collect();
+        let mut inserted = IntervalSet::new();
+        inserted.extend(intervals);
+        assert_eq!(collected, inserted);
+        assert_eq!(
+            collected.intervals(),
+            &[i(-2, 3), i(5, 6), i(8, 9), i(12, 20)]
+        );
+    }
+
+    #[test]
+    fn test_set_algebra() {
+        let a: IntervalSet = [i(0, 10), i(20, 30)].into_iter().collect();
+        let b: IntervalSet = [i(5, 25)].into_iter().collect();
+        assert_eq!(a.union(&b).intervals(), &[i(0, 30)]);
+        assert_eq!(a.intersection(&b).intervals(), &[i(5, 10), i(20, 25)]);
+        assert_eq!(a.difference(&b).intervals(), &[i(0, 4), i(26, 30)]);
+        assert_eq!(b.difference(&a).intervals(), &[i(11, 19)]);
+        assert!(a.contains(20) && !a.contains(15));
+    }
+
+    #[test]
+    fn test_gaps() {
+        let set: IntervalSet = [i(0, 3), i(6, 8), i(12, 14)].into_iter().collect();
+        assert_eq!(
+            set.gaps(i(-2, 20)).collect::<Vec<_>>(),
+            vec![i(-2, -1), i(4, 5), i(9, 11), i(15, 20)]
+        );
+        assert_eq!(set.gaps(i(1, 7)).collect::<Vec<_>>(), vec![i(4, 5)]);
+        assert_eq!(set.gaps(i(6, 8)).count(), 0);
+        assert_eq!(
+            IntervalSet::new().gaps(i(1, 2)).collect::<Vec<_>>(),
+            vec![i(1, 2)]
+        );
+    }
+}
@@ -0,0 +1,276 @@
+/*
+ * Integer helpers: gcd / lcm, modular arithmetic and the chinese remainder theorem.
+ * Example import: `use advent_of_code::helpers::math::{lcm, crt};`.
+ */
+use std::fmt::Debug;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+/// Primitive integer types the helpers in this module are generic over.
+pub trait Integer:
+    Copy
+    + Ord
+    + Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+/// Signed integers, required wherever Bézout coefficients can become negative.
+pub trait SignedInteger: Integer + Neg<Output = Self> {}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {$(
+        impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+
+            fn checked_sub(self, other: Self) -> Option<Self> {
+                <$t>::checked_sub(self, other)
+            }
+
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$t>::checked_mul(self, other)
+            }
+        }
+    )*};
+}
+
+impl_integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl SignedInteger for i8 {}
+impl SignedInteger for i16 {}
+impl SignedInteger for i32 {}
+impl SignedInteger for i64 {}
+impl SignedInteger for i128 {}
+impl SignedInteger for isize {}
+
+fn abs<T: Integer>(n: T) -> T {
+    if n < T::ZERO {
+        T::ZERO - n
+    } else {
+        n
+    }
+}
+
+/// Greatest common divisor. Always non-negative, `gcd(0, 0) == 0`.
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a, b);
+    while b != T::ZERO {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    abs(a)
+}
+
+/// Least common multiple. Panics on overflow in debug builds, see [`checked_lcm`].
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    if a == T::ZERO || b == T::ZERO {
+        return T::ZERO;
+    }
+    abs(a / gcd(a, b) * b)
+}
+
+/// Least common multiple, or `None` if it does not fit into `T`.
+pub fn checked_lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    (a / gcd(a, b)).checked_mul(b).map(abs)
+}
+
+/// Least common multiple of all values, e.g. the period of several cycles.
+/// Returns `None` for an empty iterator or on overflow.
+pub fn lcm_all<T: Integer>(values: impl IntoIterator<Item = T>) -> Option<T> {
+    let mut values = values.into_iter();
+    let first = values.next()?;
+    values.try_fold(abs(first), checked_lcm)
+}
+
+/// Sum of all values, or `None` on overflow.
+pub fn checked_sum<T: Integer>(values: impl IntoIterator<Item = T>) -> Option<T> {
+    values
+        .into_iter()
+        .try_fold(T::ZERO, |acc, n| acc.checked_add(n))
+}
+
+/// Product of all values, or `None` on overflow.
+pub fn checked_product<T: Integer>(values: impl IntoIterator<Item = T>) -> Option<T> {
+    values
+        .into_iter()
+        .try_fold(T::ONE, |acc, n| acc.checked_mul(n))
+}
+
+/// Extended euclidean algorithm.
+/// Returns `(g, x, y)` with `a * x + b * y == g` where `g` is `gcd(a, b)`.
+pub fn extended_gcd<T: SignedInteger>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::ONE, T::ZERO);
+    let (mut old_y, mut y) = (T::ZERO, T::ONE);
+    while r != T::ZERO {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < T::ZERO {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Euclidean remainder: always in `0..modulus` for a positive modulus.
+pub fn modulo<T: Integer>(a: T, modulus: T) -> T {
+    let r = a % modulus;
+    if r < T::ZERO {
+        r + abs(modulus)
+    } else {
+        r
+    }
+}
+
+/// `base.pow(exp) % modulus`, without overflowing for any `u64` modulus. A `modulus` of zero
+/// has no remainders.
+pub fn mod_pow(base: u64, exp: u64, modulus: u64) -> Option<u64> {
+    match modulus {
+        0 => return None,
+        1 => return Some(0),
+        _ => {}
+    }
+    let modulus = modulus as u128;
+    let mut base = base as u128 % modulus;
+    let mut exp = exp;
+    let mut result = 1;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+    Some(result as u64)
+}
+
+/// Multiplicative inverse of `a` modulo `modulus`, if `a` and `modulus` are coprime.
+/// A `modulus` of zero or less has no inverses.
+pub fn mod_inv(a: i64, modulus: i64) -> Option<i64> {
+    if modulus <= 0 {
+        return None;
+    }
+    let (g, x, _) = extended_gcd(modulo(a, modulus), modulus);
+    if g == 1 {
+        Some(modulo(x, modulus))
+    } else {
+        None
+    }
+}
+
+/// Chinese remainder theorem for `x ≡ residue (mod modulus)` congruences.
+///
+/// Moduli don't need to be pairwise coprime. Returns `(x, m)` where every solution is
+/// `x + k * m`, or `None` if the system is inconsistent or `m` overflows an `i64`.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let mut x: i128 = 0;
+    let mut m: i128 = 1;
+    for &(residue, modulus) in congruences {
+        let (residue, modulus) = (residue as i128, modulus as i128);
+        if modulus <= 0 {
+            return None;
+        }
+        let residue = modulo(residue, modulus);
+        let (g, p, _) = extended_gcd(m, modulus);
+        let diff = residue - x;
+        if diff % g != 0 {
+            return None;
+        }
+        let step = modulus / g;
+        let k = modulo(modulo(diff / g, step) * modulo(p, step), step);
+        x += m * k;
+        m = m.checked_mul(step)?;
+        if m > i64::MAX as i128 {
+            return None;
+        }
+        x = modulo(x, m);
+    }
+    Some((x as i64, m as i64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(gcd(12_u32, 18), 6);
+        assert_eq!(gcd(17_u64, 5), 1);
+        assert_eq!(gcd(-12_i32, 18), 6);
+        assert_eq!(gcd(0_u8, 7), 7);
+        assert_eq!(gcd(0_i64, 0), 0);
+    }
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(lcm(4_u32, 6), 12);
+        assert_eq!(lcm(-4_i32, 6), 12);
+        assert_eq!(lcm(0_u32, 6), 0);
+        assert_eq!(lcm_all([23_u64, 19, 13, 17]), Some(96577));
+        assert_eq!(lcm_all(Vec::<u32>::new()), None);
+    }
+
+    #[test]
+    fn test_checked() {
+        assert_eq!(checked_lcm(u8::MAX, 2), None);
+        assert_eq!(checked_lcm(200_u8, 100), Some(200));
+        assert_eq!(checked_sum([u32::MAX, 1]), None);
+        assert_eq!(checked_sum([1_u32, 2, 3]), Some(6));
+        assert_eq!(checked_product([1_u64 << 20, 1 << 20]), Some(1 << 40));
+        assert_eq!(checked_product([1_u32 << 20, 1 << 20]), None);
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        for (a, b) in [(240_i64, 46), (46, 240), (-240, 46), (7, 0), (0, 7)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g);
+        }
+    }
+
+    #[test]
+    fn test_modular() {
+        assert_eq!(modulo(-7_i32, 3), 2);
+        assert_eq!(mod_pow(2, 10, 1000), Some(24));
+        assert_eq!(mod_pow(u64::MAX, u64::MAX, u64::MAX - 1), Some(1));
+        assert_eq!(mod_pow(7, 3, 1), Some(0));
+        assert_eq!(mod_pow(7, 3, 0), None);
+        assert_eq!(mod_inv(3, 11), Some(4));
+        assert_eq!(mod_inv(-3, 11), Some(7));
+        assert_eq!(mod_inv(4, 8), None);
+        assert_eq!(mod_inv(3, 0), None);
+        assert_eq!(mod_inv(3, -11), None);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(3, 4), (1, 6)]), Some((7, 12)));
+        assert_eq!(crt(&[(0, 4), (1, 6)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(-1, 5)]), Some((4, 5)));
+        assert_eq!(crt(&[(0, i64::MAX), (0, i64::MAX - 1)]), None);
+    }
+}
@@ -0,0 +1,202 @@
+/*
+ * Depth-first branch and bound for "maximize X in N minutes" style puzzles.
+ * Example import: `use advent_of_code::helpers::optimize::{BranchAndBound, Problem};`.
+ */
+use std::collections::HashSet;
+use std::hash::Hash;
+
+/// A maximization problem over a tree of states.
+pub trait Problem {
+    type State: Clone;
+    type Key: Hash + Eq;
+    type Value: Ord + Copy;
+
+    /// States reachable in one move. No successors means the state is final.
+    fn successors(&self, state: &Self::State) -> Vec<Self::State>;
+
+    /// The value achieved if no further moves are made from `state`.
+    fn value(&self, state: &Self::State) -> Self::Value;
+
+    /// An optimistic estimate: no state reachable from `state` may have a higher value.
+    /// The tighter the bound, the more of the tree gets pruned.
+    fn upper_bound(&self, state: &Self::State) -> Self::Value;
+
+    /// Identifies states that have the same outcome, so each one is only explored once.
+    fn key(&self, state: &Self::State) -> Self::Key;
+}
+
+/// The best state found by [`BranchAndBound::maximize`], with its value.
+#[derive(Debug, Clone)]
+pub struct Best<S, V> {
+    pub state: S,
+    pub value: V,
+}
+
+/// Configures the search. Exact by default, see [`BranchAndBound::beam_width`].
+#[derive(Debug, Clone, Copy)]
+pub struct BranchAndBound {
+    memoize: bool,
+    beam_width: Option<usize>,
+}
+
+impl Default for BranchAndBound {
+    fn default() -> Self {
+        Self {
+            memoize: true,
+            beam_width: None,
+        }
+    }
+}
+
+impl BranchAndBound {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether to skip states whose key was seen before. Enabled by default.
+    pub fn memoize(mut self, memoize: bool) -> Self {
+        self.memoize = memoize;
+        self
+    }
+
+    /// Only explore the `width` most promising successors of every state, ranked by their
+    /// upper bound. Much faster on wide trees, but the result is no longer guaranteed
+    /// to be optimal.
+    pub fn beam_width(mut self, width: usize) -> Self {
+        self.beam_width = Some(width);
+        self
+    }
+
+    pub fn maximize<P: Problem>(&self, problem: &P, initial: P::State) -> Best<P::State, P::Value> {
+        let mut best = Best {
+            value: problem.value(&initial),
+            state: initial.clone(),
+        };
+        let mut seen = HashSet::new();
+        let mut stack = vec![(problem.upper_bound(&initial), initial)];
+
+        while let Some((bound, state)) = stack.pop() {
+            // the best value may have improved since this state was pushed
+            if bound <= best.value {
+                continue;
+            }
+            if self.memoize && !seen.insert(problem.key(&state)) {
+                continue;
+            }
+            let value = problem.value(&state);
+            if value > best.value {
+                best = Best {
+                    value,
+                    state: state.clone(),
+                };
+            }
+
+            let mut successors: Vec<(P::Value, P::State)> = problem
+                .successors(&state)
+                .into_iter()
+                .map(|s| (problem.upper_bound(&s), s))
+                .filter(|(bound, _)| *bound > best.value)
+                .collect();
+            // most promising last, so it is popped off the stack first
+            successors.sort_by_key(|(bound, _)| *bound);
+            if let Some(width) = self.beam_width {
+                let skip = successors.len().saturating_sub(width);
+                successors.drain(..skip);
+            }
+            stack.extend(successors);
+        }
+
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0/1 knapsack: pick items, (weight, value), without exceeding the capacity.
+    struct Knapsack {
+        items: Vec<(u32, u32)>,
+        capacity: u32,
+    }
+
+    #[derive(Debug, Clone)]
+    struct Packed {
+        next: usize,
+        weight: u32,
+        value: u32,
+    }
+
+    impl Problem for Knapsack {
+        type State = Packed;
+        type Key = (usize, u32, u32);
+        type Value = u32;
+
+        fn successors(&self, state: &Packed) -> Vec<Packed> {
+            let Some(&(weight, value)) = self.items.get(state.next) else {
+                return vec![];
+            };
+            let skip = Packed {
+                next: state.next + 1,
+                ..*state
+            };
+            if state.weight + weight > self.capacity {
+                return vec![skip];
+            }
+            let take = Packed {
+                next: state.next + 1,
+                weight: state.weight + weight,
+                value: state.value + value,
+            };
+            vec![skip, take]
+        }
+
+        fn value(&self, state: &Packed) -> u32 {
+            state.value
+        }
+
+        fn upper_bound(&self, state: &Packed) -> u32 {
+            state.value + self.items[state.next..].iter().map(|i| i.1).sum::<u32>()
+        }
+
+        fn key(&self, state: &Packed) -> Self::Key {
+            (state.next, state.weight, state.value)
+        }
+    }
+
+    fn knapsack() -> Knapsack {
+        Knapsack {
+            items: vec![(12, 4), (2, 2), (1, 1), (1, 2), (4, 10)],
+            capacity: 15,
+        }
+    }
+
+    fn start() -> Packed {
+        Packed {
+            next: 0,
+            weight: 0,
+            value: 0,
+        }
+    }
+
+    #[test]
+    fn finds_optimum() {
+        let best = BranchAndBound::new().maximize(&knapsack(), start());
+        assert_eq!(best.value, 15);
+        assert_eq!(best.state.weight, 8);
+
+        let unmemoized = BranchAndBound::new()
+            .memoize(false)
+            .maximize(&knapsack(), start());
+        assert_eq!(unmemoized.value, 15);
+    }
+
+    #[test]
+    fn beam_search_is_a_lower_bound() {
+        let best = BranchAndBound::new()
+            .beam_width(1)
+            .maximize(&knapsack(), start());
+        assert!(best.value <= 15);
+        assert!(best.state.weight <= 15);
+    }
+}
@@ -0,0 +1,294 @@
+/*
+ * Interactive step-through of simulations in the terminal, for debugging against the example.
+ * Example import: `use advent_of_code::helpers::stepper::{self, Steppable};`.
+ */
+use std::env;
+use std::io::{self, BufRead, Write};
+use std::sync::OnceLock;
+
+/// A simulation that advances one step at a time.
+pub trait Steppable: Clone {
+    /// Advances by one step. Returns `false`, leaving the state as it is, once the
+    /// simulation is over.
+    fn step(&mut self) -> bool;
+
+    /// The current state, drawn for the terminal.
+    fn render(&self) -> String;
+
+    /// Whether `condition`, as typed after `until`, holds for the current state.
+    /// By default whether the rendered state contains it.
+    fn check(&self, condition: &str) -> bool {
+        self.render().contains(condition)
+    }
+}
+
+/// Every state a simulation went through, so it can be stepped back and forth.
+#[derive(Debug, Clone)]
+pub struct Stepper<S> {
+    history: Vec<S>,
+    at: usize,
+    finished: bool,
+}
+
+impl<S: Steppable> Stepper<S> {
+    pub fn new(initial: S) -> Self {
+        Self {
+            history: vec![initial],
+            at: 0,
+            finished: false,
+        }
+    }
+
+    /// The number of steps taken to reach the current state.
+    pub fn step_number(&self) -> usize {
+        self.at
+    }
+
+    pub fn current(&self) -> &S {
+        &self.history[self.at]
+    }
+
+    /// Whether the simulation is over and the current state is the last one.
+    pub fn is_finished(&self) -> bool {
+        self.finished && self.at + 1 == self.history.len()
+    }
+
+    /// Moves one step forward, simulating it unless it was seen before. Returns `false` at the end.
+    pub fn forward(&mut self) -> bool {
+        if self.at + 1 < self.history.len() {
+            self.at += 1;
+            return true;
+        }
+        if self.finished {
+            return false;
+        }
+        let mut state = self.history[self.at].clone();
+        if state.step() {
+            self.history.push(state);
+            self.at += 1;
+            true
+        } else {
+            self.finished = true;
+            false
+        }
+    }
+
+    /// Moves one step back. Returns `false` at the initial state.
+    pub fn back(&mut self) -> bool {
+        if self.at == 0 {
+            return false;
+        }
+        self.at -= 1;
+        true
+    }
+
+    /// Moves to the state after `step` steps, or the last state if the simulation ends before.
+    pub fn jump(&mut self, step: usize) -> bool {
+        if step < self.history.len() {
+            self.at = step;
+            return true;
+        }
+        self.at = self.history.len() - 1;
+        while self.at < step {
+            if !self.forward() {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Moves forward at least one step, until `condition` holds. Stops at the last state if
+    /// it never does.
+    pub fn run_until(&mut self, condition: impl Fn(&S) -> bool) -> bool {
+        while self.forward() {
+            if condition(self.current()) {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Reads commands from `input` until `q` or the end of the input, printing the current
+    /// state to `output` after each one.
+    pub fn interact(
+        &mut self,
+        name: &str,
+        input: impl BufRead,
+        output: &mut impl Write,
+    ) -> io::Result<()> {
+        self.print(name, output)?;
+        for line in input.lines() {
+            let line = line?;
+            let (command, argument) = line
+                .trim()
+                .split_once(' ')
+                .map_or((line.trim(), ""), |(c, a)| (c, a.trim()));
+            let count = || argument.parse::<usize>().ok();
+            let message = match command {
+                "" | "n" | "next" => {
+                    let count = count().unwrap_or(1);
+                    let moved = (0..count).take_while(|_| self.forward()).count();
+                    (moved < count).then_some("the simulation is over")
+                }
+                "b" | "back" => {
+                    let count = count().unwrap_or(1);
+                    let moved = (0..count).take_while(|_| self.back()).count();
+                    (moved < count).then_some("this is the initial state")
+                }
+                "j" | "jump" => match count() {
+                    Some(step) => (!self.jump(step)).then_some("the simulation is over"),
+                    None => Some("usage: `j <step>`"),
+                },
+                "u" | "until" if !argument.is_empty() => {
+                    let found = self.run_until(|state| state.check(argument));
+                    (!found).then_some("the condition never holds")
+                }
+                "q" | "quit" => return Ok(()),
+                _ => Some(HELP),
+            };
+            if let Some(message) = message {
+                writeln!(output, "{message}")?;
+            }
+            self.print(name, output)?;
+        }
+        Ok(())
+    }
+
+    fn print(&self, name: &str, output: &mut impl Write) -> io::Result<()> {
+        let end = if self.is_finished() { ", finished" } else { "" };
+        writeln!(output, "--- {name}, step {}{end} ---", self.at)?;
+        writeln!(output, "{}", self.current().render().trim_end())?;
+        write!(output, "> ")?;
+        output.flush()
+    }
+}
+
+const HELP: &str = "commands:
+  n [count]        next step, also just Enter
+  b [count]        step back
+  j <step>         jump to a step
+  u <condition>    run until the condition holds
+  q                quit and continue solving";
+
+fn parse_step_flag(mut args: impl Iterator<Item = String>) -> bool {
+    args.any(|arg| arg == "--step")
+}
+
+/// Whether the day runs with `--step`.
+pub fn enabled() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    *ENABLED.get_or_init(|| parse_step_flag(env::args().skip(1)))
+}
+
+/// Steps through the simulation starting at `initial` on stdin and stderr if the day runs with
+/// `--step`, does nothing otherwise.
+///
+/// ```ignore
+/// stepper::step_through("14-part-one", scene.clone());
+/// ```
+pub fn step_through<S: Steppable>(name: &str, initial: S) {
+    if !enabled() {
+        return;
+    }
+    let mut stepper = Stepper::new(initial);
+    let result = stepper.interact(name, io::stdin().lock(), &mut io::stderr());
+    if let Err(err) = result {
+        eprintln!("Could not step through {name}: {err}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts up to `end`.
+    #[derive(Debug, Clone)]
+    struct Counter {
+        value: u32,
+        end: u32,
+    }
+
+    impl Steppable for Counter {
+        fn step(&mut self) -> bool {
+            if self.value == self.end {
+                return false;
+            }
+            self.value += 1;
+            true
+        }
+
+        fn render(&self) -> String {
+            format!("value={}", self.value)
+        }
+    }
+
+    fn counter(end: u32) -> Stepper<Counter> {
+        Stepper::new(Counter { value: 0, end })
+    }
+
+    #[test]
+    fn steps_back_and_forth() {
+        let mut stepper = counter(3);
+        assert!(!stepper.back());
+        assert!(stepper.forward() && stepper.forward());
+        assert!(stepper.back());
+        assert_eq!((stepper.step_number(), stepper.current().value), (1, 1));
+        assert!(stepper.forward() && stepper.forward());
+        assert_eq!(stepper.current().value, 3);
+        assert!(!stepper.is_finished());
+        assert!(!stepper.forward());
+        assert!(stepper.is_finished());
+    }
+
+    #[test]
+    fn jumps() {
+        let mut stepper = counter(10);
+        assert!(stepper.jump(7));
+        assert_eq!(stepper.current().value, 7);
+        assert!(stepper.jump(2));
+        assert_eq!(stepper.current().value, 2);
+        assert!(!stepper.jump(20));
+        assert_eq!(stepper.current().value, 10);
+    }
+
+    #[test]
+    fn runs_until_condition() {
+        let mut stepper = counter(10);
+        assert!(stepper.run_until(|c| c.value % 4 == 0));
+        assert_eq!(stepper.current().value, 4);
+        assert!(stepper.run_until(|c| c.value % 4 == 0));
+        assert_eq!(stepper.current().value, 8);
+        assert!(!stepper.run_until(|c| c.value == 0));
+        assert_eq!(stepper.current().value, 10);
+    }
+
+    #[test]
+    fn interactive_commands() {
+        let mut stepper = counter(5);
+        let mut output = Vec::new();
+        let input = "n\n\nn 2\nb\nj 1\nu value=5\nn\nq\nn\n";
+        stepper
+            .interact("counter", input.as_bytes(), &mut output)
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let states = output
+            .lines()
+            .filter_map(|line| line.strip_prefix("value="))
+            .collect::<Vec<_>>();
+        assert_eq!(states, ["0", "1", "2", "4", "3", "1", "5", "5"]);
+        assert!(output.contains("--- counter, step 5, finished ---"));
+        assert!(output.contains("the simulation is over"));
+    }
+
+    #[test]
+    fn test_parse_step_flag() {
+        let args = |a: &[&str]| {
+            a.iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>()
+                .into_iter()
+        };
+        assert!(parse_step_flag(args(&["-v", "--step"])));
+        assert!(!parse_step_flag(args(&["--param", "row=1"])));
+    }
+}
@@ -0,0 +1,681 @@
+/*
+ * Frame recording for simulations, exported as PPM images, an animated GIF or played back
+ * in the terminal. Pure Rust, no GPU or windowing system needed.
+ * Example import: `use advent_of_code::helpers::viz::{self, ColorMap};`.
+ */
+use std::collections::HashMap;
+use std::env;
+use std::fmt::Display;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::thread;
+use std::time::Duration;
+
+pub type Rgb = [u8; 3];
+
+/// Colors of the characters in a character grid, with a fallback for unmapped ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColorMap {
+    colors: HashMap<char, Rgb>,
+    fallback: Rgb,
+}
+
+impl ColorMap {
+    pub fn new(fallback: Rgb) -> Self {
+        Self {
+            colors: HashMap::new(),
+            fallback,
+        }
+    }
+
+    pub fn with(mut self, c: char, color: Rgb) -> Self {
+        self.colors.insert(c, color);
+        self
+    }
+
+    pub fn color(&self, c: char) -> Rgb {
+        self.colors.get(&c).copied().unwrap_or(self.fallback)
+    }
+}
+
+/// Dark background for `.` and spaces, grey walls for `#`, white for everything else.
+impl Default for ColorMap {
+    fn default() -> Self {
+        Self::new([255, 255, 255])
+            .with('.', [15, 15, 35])
+            .with(' ', [15, 15, 35])
+            .with('#', [110, 110, 110])
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Frame {
+    /// Rows of characters, colored with the recording's [`ColorMap`].
+    Chars(Vec<Vec<char>>),
+    Pixels {
+        width: usize,
+        height: usize,
+        pixels: Vec<Rgb>,
+    },
+}
+
+/// A frame turned into pixels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<Rgb>,
+}
+
+/// Where [`Recording::export`] sends the frames.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Output {
+    /// One image per frame, `dir/0000.ppm`, `dir/0001.ppm`, ...
+    Ppm(PathBuf),
+    Gif(PathBuf),
+    /// Plays the frames on stderr.
+    Terminal,
+}
+
+/// Frames pushed by a simulation, in order.
+#[derive(Debug, Clone)]
+pub struct Recording {
+    colors: ColorMap,
+    cell_size: usize,
+    every: usize,
+    pushed: usize,
+    frames: Vec<Frame>,
+}
+
+impl Default for Recording {
+    fn default() -> Self {
+        Self {
+            colors: ColorMap::default(),
+            cell_size: 4,
+            every: 1,
+            pushed: 0,
+            frames: Vec::new(),
+        }
+    }
+}
+
+impl Recording {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn colors(mut self, colors: ColorMap) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    /// Width and height in pixels of a character when exporting images. Defaults to 4.
+    pub fn cell_size(mut self, size: usize) -> Self {
+        self.cell_size = size.max(1);
+        self
+    }
+
+    /// Only keep every `n`th pushed frame, for simulations with many steps.
+    pub fn every(mut self, n: usize) -> Self {
+        self.every = n.max(1);
+        self
+    }
+
+    /// Pushes the lines of `grid`'s `Display` output as a character frame.
+    pub fn push_chars(&mut self, grid: impl Display) {
+        if self.keep() {
+            let rows = grid
+                .to_string()
+                .lines()
+                .map(|line| line.chars().collect())
+                .collect();
+            self.frames.push(Frame::Chars(rows));
+        }
+    }
+
+    /// Pushes a frame of `width * height` pixels, row by row.
+    pub fn push_pixels(&mut self, width: usize, height: usize, pixels: Vec<Rgb>) {
+        assert_eq!(pixels.len(), width * height, "frame size does not match");
+        if self.keep() {
+            self.frames.push(Frame::Pixels {
+                width,
+                height,
+                pixels,
+            });
+        }
+    }
+
+    fn keep(&mut self) -> bool {
+        self.pushed += 1;
+        (self.pushed - 1).is_multiple_of(self.every)
+    }
+
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    /// Every frame as an image, padded with the color of `.` to the size of the largest one.
+    pub fn images(&self) -> Vec<Image> {
+        let images = self
+            .frames
+            .iter()
+            .map(|frame| self.rasterize(frame))
+            .collect::<Vec<_>>();
+        let width = images.iter().map(|i| i.width).max().unwrap_or(0);
+        let height = images.iter().map(|i| i.height).max().unwrap_or(0);
+        let background = self.colors.color('.');
+        images
+            .into_iter()
+            .map(|image| {
+                let mut pixels = vec![background; width * height];
+                for y in 0..image.height {
+                    pixels[y * width..y * width + image.width]
+                        .copy_from_slice(&image.pixels[y * image.width..(y + 1) * image.width]);
+                }
+                Image {
+                    width,
+                    height,
+                    pixels,
+                }
+            })
+            .collect()
+    }
+
+    fn rasterize(&self, frame: &Frame) -> Image {
+        match frame {
+            Frame::Pixels {
+                width,
+                height,
+                pixels,
+            } => Image {
+                width: *width,
+                height: *height,
+                pixels: pixels.clone(),
+            },
+            Frame::Chars(rows) => {
+                let size = self.cell_size;
+                let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+                let (width, height) = (columns * size, rows.len() * size);
+                let mut pixels = vec![self.colors.color('.'); width * height];
+                for (row, line) in rows.iter().enumerate() {
+                    for (column, &c) in line.iter().enumerate() {
+                        let color = self.colors.color(c);
+                        for y in row * size..(row + 1) * size {
+                            pixels[y * width + column * size..y * width + (column + 1) * size]
+                                .fill(color);
+                        }
+                    }
+                }
+                Image {
+                    width,
+                    height,
+                    pixels,
+                }
+            }
+        }
+    }
+
+    /// Writes every frame as a binary PPM image into `dir`, which is created if needed.
+    pub fn write_ppm(&self, dir: &Path) -> io::Result<()> {
+        fs::create_dir_all(dir)?;
+        for (i, image) in self.images().iter().enumerate() {
+            let mut bytes = format!("P6\n{} {}\n255\n", image.width, image.height).into_bytes();
+            bytes.extend(image.pixels.iter().flatten());
+            fs::write(dir.join(format!("{i:04}.ppm")), bytes)?;
+        }
+        Ok(())
+    }
+
+    /// Writes all frames as a looping GIF. Fails if the frames use more than 256 colors.
+    pub fn write_gif(&self, path: &Path, fps: u32) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, gif::encode(&self.images(), fps)?)
+    }
+
+    /// Plays the frames in the terminal with 24-bit ANSI colors. Character frames are printed
+    /// as they are, pixel frames with two pixels per character.
+    pub fn play(&self, fps: u32, out: &mut impl Write) -> io::Result<()> {
+        let delay = Duration::from_secs_f64(1_f64 / fps.max(1) as f64);
+        for (i, frame) in self.frames.iter().enumerate() {
+            // clear the screen once, then only move the cursor back to the top left
+            let mut text = String::from(if i == 0 { "\x1b[2J\x1b[H" } else { "\x1b[H" });
+            match frame {
+                Frame::Chars(rows) => {
+                    for row in rows {
+                        for &c in row {
+                            let [r, g, b] = self.colors.color(c);
+                            text += &format!("\x1b[38;2;{r};{g};{b}m{c}");
+                        }
+                        text += "\x1b[0m\n";
+                    }
+                }
+                Frame::Pixels {
+                    width,
+                    height,
+                    pixels,
+                } => {
+                    for y in (0..*height).step_by(2) {
+                        for x in 0..*width {
+                            let [r, g, b] = pixels[y * width + x];
+                            let [r2, g2, b2] = match y + 1 < *height {
+                                true => pixels[(y + 1) * width + x],
+                                false => [0, 0, 0],
+                            };
+                            text += &format!("\x1b[38;2;{r};{g};{b}m\x1b[48;2;{r2};{g2};{b2}m▀");
+                        }
+                        text += "\x1b[0m\n";
+                    }
+                }
+            }
+            out.write_all(text.as_bytes())?;
+            out.flush()?;
+            thread::sleep(delay);
+        }
+        Ok(())
+    }
+
+    /// Exports the frames to `output`, reporting where they went on stderr.
+    pub fn export(&self, output: &Output, fps: u32) -> io::Result<()> {
+        match output {
+            Output::Ppm(dir) => {
+                self.write_ppm(dir)?;
+                eprintln!("Wrote {} frames to {}", self.frames.len(), dir.display());
+            }
+            Output::Gif(path) => {
+                self.write_gif(path, fps)?;
+                eprintln!("Wrote {} frames to {}", self.frames.len(), path.display());
+            }
+            Output::Terminal => self.play(fps, &mut io::stderr())?,
+        }
+        Ok(())
+    }
+}
+
+/// How to record, from the `--viz`, `--fps` and `--viz-every` arguments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VizArgs {
+    pub format: Option<String>,
+    pub fps: u32,
+    pub every: usize,
+}
+
+fn parse_viz_args(args: impl Iterator<Item = String>) -> VizArgs {
+    let mut viz = VizArgs {
+        format: None,
+        fps: 10,
+        every: 1,
+    };
+    let mut args = args.peekable();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().unwrap_or_default();
+        match arg.as_str() {
+            "--viz" => viz.format = Some(value()),
+            "--fps" => viz.fps = value().parse().unwrap_or(viz.fps),
+            "--viz-every" => viz.every = value().parse().unwrap_or(viz.every),
+            _ => {}
+        }
+    }
+    viz
+}
+
+fn viz_args() -> &'static VizArgs {
+    static ARGS: OnceLock<VizArgs> = OnceLock::new();
+    ARGS.get_or_init(|| parse_viz_args(env::args().skip(1)))
+}
+
+/// A recording that [`finish`](Recorder::finish)es into the output chosen on the command line.
+#[derive(Debug)]
+pub struct Recorder {
+    recording: Recording,
+    output: Output,
+    fps: u32,
+}
+
+impl Recorder {
+    pub fn push_chars(&mut self, grid: impl Display) {
+        self.recording.push_chars(grid);
+    }
+
+    pub fn push_pixels(&mut self, width: usize, height: usize, pixels: Vec<Rgb>) {
+        self.recording.push_pixels(width, height, pixels);
+    }
+
+    /// Exports the recording, printing any error to stderr instead of failing the part.
+    pub fn finish(self) {
+        if let Err(err) = self.recording.export(&self.output, self.fps) {
+            eprintln!("Could not export visualization: {err}");
+        }
+    }
+}
+
+/// Starts recording `name` if the day runs with `--viz gif`, `--viz ppm` or `--viz term`,
+/// so simulations only collect frames when asked to. Images go to `viz/{name}.gif` or
+/// `viz/{name}/`. `--fps` sets the playback speed (default 10) and `--viz-every n` only
+/// keeps every `n`th frame.
+///
+/// ```ignore
+/// let mut recorder = viz::record("14-part-one", ColorMap::default());
+/// if let Some(recorder) = &mut recorder {
+///     recorder.push_chars(&scene);
+/// }
+/// ```
+pub fn record(name: &str, colors: ColorMap) -> Option<Recorder> {
+    let args = viz_args();
+    let output = match args.format.as_deref()? {
+        "gif" => Output::Gif(Path::new("viz").join(format!("{name}.gif"))),
+        "ppm" => Output::Ppm(Path::new("viz").join(name)),
+        "term" => Output::Terminal,
+        other => {
+            eprintln!("Unknown visualization `{other}`, expected one of: gif, ppm, term");
+            return None;
+        }
+    };
+    Some(Recorder {
+        recording: Recording::new().colors(colors).every(args.every),
+        output,
+        fps: args.fps,
+    })
+}
+
+/// A minimal GIF89a encoder: one global palette and LZW compressed frames.
+mod gif {
+    use std::collections::HashMap;
+    use std::io;
+
+    use super::{Image, Rgb};
+
+    const MAX_CODE: u16 = 4095;
+
+    pub fn encode(images: &[Image], fps: u32) -> io::Result<Vec<u8>> {
+        let mut palette: Vec<Rgb> = Vec::new();
+        let mut index = HashMap::new();
+        for pixel in images.iter().flat_map(|image| &image.pixels) {
+            index.entry(*pixel).or_insert_with(|| {
+                palette.push(*pixel);
+                palette.len() - 1
+            });
+        }
+        if palette.len() > 256 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("GIF supports 256 colors, frames use {}", palette.len()),
+            ));
+        }
+        // the palette has 2^(bits) entries, at least 2
+        let bits = (1..=8).find(|b| 1 << b >= palette.len()).unwrap_or(8);
+        palette.resize(1 << bits, [0, 0, 0]);
+
+        let (width, height) = images.first().map_or((0, 0), |i| (i.width, i.height));
+        let (Ok(width), Ok(height)) = (u16::try_from(width), u16::try_from(height)) else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("GIF frames are at most 65535 pixels wide and high, not {width}x{height}"),
+            ));
+        };
+        let mut out = b"GIF89a".to_vec();
+        out.extend(width.to_le_bytes());
+        out.extend(height.to_le_bytes());
+        out.extend([0x80 | 0x70 | (bits as u8 - 1), 0, 0]);
+        out.extend(palette.iter().flatten());
+        // loop forever
+        out.extend(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00");
+
+        let delay = (100 / fps.max(1)).max(1) as u16;
+        let min_code_size = (bits as u8).max(2);
+        for image in images {
+            out.extend([0x21, 0xf9, 0x04, 0x00]);
+            out.extend(delay.to_le_bytes());
+            out.extend([0x00, 0x00]);
+
+            out.push(0x2c);
+            out.extend([0, 0, 0, 0]);
+            out.extend(width.to_le_bytes());
+            out.extend(height.to_le_bytes());
+            out.push(0);
+
+            let indices = image
+                .pixels
+                .iter()
+                .map(|pixel| index[pixel] as u8)
+                .collect::<Vec<_>>();
+            out.push(min_code_size);
+            for block in lzw(min_code_size, &indices).chunks(255) {
+                out.push(block.len() as u8);
+                out.extend(block);
+            }
+            out.push(0);
+        }
+        out.push(0x3b);
+        Ok(out)
+    }
+
+    /// Variable width codes, packed least significant bit first.
+    struct Codes {
+        bytes: Vec<u8>,
+        buffer: u32,
+        buffered: u8,
+        size: u8,
+        next: u16,
+    }
+
+    impl Codes {
+        fn write(&mut self, code: u16) {
+            self.buffer |= (code as u32) << self.buffered;
+            self.buffered += self.size;
+            while self.buffered >= 8 {
+                self.bytes.push(self.buffer as u8);
+                self.buffer >>= 8;
+                self.buffered -= 8;
+            }
+            // the decoder widens its codes once the next free code no longer fits
+            if self.next >= 1 << self.size && self.size < 12 {
+                self.size += 1;
+            }
+        }
+
+        fn finish(mut self) -> Vec<u8> {
+            if self.buffered > 0 {
+                self.bytes.push(self.buffer as u8);
+            }
+            self.bytes
+        }
+    }
+
+    pub(super) fn lzw(min_code_size: u8, indices: &[u8]) -> Vec<u8> {
+        let clear = 1 << min_code_size;
+        let end = clear + 1;
+        let mut codes = Codes {
+            bytes: Vec::new(),
+            buffer: 0,
+            buffered: 0,
+            size: min_code_size + 1,
+            next: end + 1,
+        };
+        let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+        codes.write(clear);
+
+        let mut indices = indices.iter();
+        if let Some(&first) = indices.next() {
+            let mut prefix = first as u16;
+            for &index in indices {
+                if let Some(&code) = table.get(&(prefix, index)) {
+                    prefix = code;
+                    continue;
+                }
+                codes.write(prefix);
+                if codes.next >= MAX_CODE {
+                    codes.write(clear);
+                    table.clear();
+                    codes.next = end + 1;
+                    codes.size = min_code_size + 1;
+                } else {
+                    table.insert((prefix, index), codes.next);
+                    codes.next += 1;
+                }
+                prefix = index as u16;
+            }
+            codes.write(prefix);
+        }
+        codes.write(end);
+        codes.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Decodes GIF LZW data, to check the encoder against.
+    fn unlzw(min_code_size: u8, bytes: &[u8]) -> Vec<u8> {
+        let clear = 1u16 << min_code_size;
+        let end = clear + 1;
+        let initial = || {
+            (0..clear)
+                .map(|i| vec![i as u8])
+                .chain([vec![], vec![]])
+                .collect::<Vec<_>>()
+        };
+        let mut table = initial();
+        let mut size = min_code_size + 1;
+        let (mut bit, mut out, mut previous) = (0, Vec::new(), None::<Vec<u8>>);
+        loop {
+            let code = (0..size as usize).fold(0u16, |code, i| {
+                let b = bit + i;
+                code | (((bytes[b / 8] >> (b % 8)) & 1) as u16) << i
+            });
+            bit += size as usize;
+            if code == clear {
+                table = initial();
+                size = min_code_size + 1;
+                previous = None;
+                continue;
+            }
+            if code == end {
+                return out;
+            }
+            let entry = match (table.get(code as usize), &previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(prev)) => [prev.clone(), vec![prev[0]]].concat(),
+                (None, None) => panic!("invalid code {code}"),
+            };
+            if let Some(prev) = previous {
+                table.push([prev, vec![entry[0]]].concat());
+            }
+            if table.len() == 1 << size && size < 12 {
+                size += 1;
+            }
+            out.extend(&entry);
+            previous = Some(entry);
+        }
+    }
+
+    #[test]
+    fn lzw_round_trip() {
+        let mut rng = crate::generate::Rng::new(3);
+        for (min_code_size, len, colors) in [(2, 0, 1), (2, 1, 4), (2, 5000, 4), (8, 20000, 256)] {
+            let indices = (0..len)
+                .map(|i| match rng.chance(70) {
+                    true => (i / 7 % colors) as u8,
+                    false => rng.below(colors) as u8,
+                })
+                .collect::<Vec<_>>();
+            let encoded = gif::lzw(min_code_size, &indices);
+            assert_eq!(unlzw(min_code_size, &encoded), indices);
+        }
+    }
+
+    #[test]
+    fn rasterizes_and_pads_frames() {
+        let colors = ColorMap::new([9, 9, 9]).with('.', [0, 0, 0]);
+        let mut recording = Recording::new().colors(colors).cell_size(2);
+        recording.push_chars("#");
+        recording.push_chars(".x\n..");
+        let images = recording.images();
+        assert_eq!((images[0].width, images[0].height), (4, 4));
+        assert_eq!(images[0].pixels[0], [9, 9, 9]);
+        assert_eq!(images[0].pixels[2], [0, 0, 0]);
+        assert_eq!(
+            images[1].pixels[..4],
+            [[0, 0, 0], [0, 0, 0], [9, 9, 9], [9, 9, 9]]
+        );
+    }
+
+    #[test]
+    fn keeps_every_nth_frame() {
+        let mut recording = Recording::new().every(3);
+        for i in 0..7 {
+            recording.push_pixels(1, 1, vec![[i, 0, 0]]);
+        }
+        let kept = recording
+            .frames()
+            .iter()
+            .map(|frame| match frame {
+                Frame::Pixels { pixels, .. } => pixels[0][0],
+                Frame::Chars(_) => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(kept, [0, 3, 6]);
+    }
+
+    #[test]
+    fn encodes_gif() {
+        let mut recording = Recording::new();
+        recording.push_pixels(2, 1, vec![[255, 0, 0], [0, 0, 255]]);
+        recording.push_pixels(2, 1, vec![[0, 0, 255], [0, 0, 255]]);
+        let gif = gif::encode(&recording.images(), 10).unwrap();
+        assert_eq!(&gif[..6], b"GIF89a");
+        assert_eq!(&gif[6..10], [2, 0, 1, 0]);
+        // 2 colors: a palette of 2 entries right after the screen descriptor
+        assert_eq!(gif[10] & 0x07, 0);
+        assert_eq!(&gif[13..19], [255, 0, 0, 0, 0, 255]);
+        assert_eq!(gif.iter().filter(|&&b| b == 0x2c).count(), 2);
+        assert_eq!(gif.last(), Some(&0x3b));
+    }
+
+    #[test]
+    fn too_many_colors_for_gif() {
+        let mut recording = Recording::new();
+        recording.push_pixels(
+            300,
+            1,
+            (0..300).map(|i| [i as u8, (i / 256) as u8, 0]).collect(),
+        );
+        assert!(gif::encode(&recording.images(), 10).is_err());
+    }
+
+    #[test]
+    fn plays_in_terminal() {
+        let mut recording = Recording::new().colors(ColorMap::new([1, 2, 3]));
+        recording.push_chars("ab");
+        let mut out = Vec::new();
+        recording.play(1000, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(
+            out,
+            "\x1b[2J\x1b[H\x1b[38;2;1;2;3ma\x1b[38;2;1;2;3mb\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_viz_args() {
+        let args = |a: &[&str]| {
+            a.iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>()
+                .into_iter()
+        };
+        assert_eq!(parse_viz_args(args(&["-v"])).format, None);
+        assert_eq!(
+            parse_viz_args(args(&["--viz", "gif", "--fps", "25", "--viz-every", "5"])),
+            VizArgs {
+                format: Some("gif".to_string()),
+                fps: 25,
+                every: 5,
+            }
+        );
+    }
+}
@@ -0,0 +1,220 @@
+/*
+ * Sparse 3D voxel grids: face neighbors, flood fill and surface area.
+ * Example import: `use advent_of_code::helpers::voxel::{Voxel, VoxelGrid};`.
+ */
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Voxel {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl Voxel {
+    pub fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+
+    /// The six voxels sharing a face with this one.
+    pub fn neighbors(self) -> impl Iterator<Item = Voxel> {
+        let Voxel { x, y, z } = self;
+        [
+            Voxel::new(x + 1, y, z),
+            Voxel::new(x - 1, y, z),
+            Voxel::new(x, y + 1, z),
+            Voxel::new(x, y - 1, z),
+            Voxel::new(x, y, z + 1),
+            Voxel::new(x, y, z - 1),
+        ]
+        .into_iter()
+    }
+}
+
+/// An axis aligned box of voxels, bounds inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoundingBox {
+    pub min: Voxel,
+    pub max: Voxel,
+}
+
+impl BoundingBox {
+    pub fn contains(&self, voxel: Voxel) -> bool {
+        (self.min.x..=self.max.x).contains(&voxel.x)
+            && (self.min.y..=self.max.y).contains(&voxel.y)
+            && (self.min.z..=self.max.z).contains(&voxel.z)
+    }
+
+    /// The box grown by `by` voxels in every direction.
+    pub fn expand(&self, by: i32) -> Self {
+        Self {
+            min: Voxel::new(self.min.x - by, self.min.y - by, self.min.z - by),
+            max: Voxel::new(self.max.x + by, self.max.y + by, self.max.z + by),
+        }
+    }
+
+    fn include(&mut self, voxel: Voxel) {
+        self.min = Voxel::new(
+            self.min.x.min(voxel.x),
+            self.min.y.min(voxel.y),
+            self.min.z.min(voxel.z),
+        );
+        self.max = Voxel::new(
+            self.max.x.max(voxel.x),
+            self.max.y.max(voxel.y),
+            self.max.z.max(voxel.z),
+        );
+    }
+}
+
+/// A sparse set of filled voxels that keeps track of its bounding box.
+#[derive(Debug, Clone, Default)]
+pub struct VoxelGrid {
+    filled: HashSet<Voxel>,
+    bounds: Option<BoundingBox>,
+}
+
+impl VoxelGrid {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, voxel: Voxel) -> bool {
+        match &mut self.bounds {
+            Some(bounds) => bounds.include(voxel),
+            None => {
+                self.bounds = Some(BoundingBox {
+                    min: voxel,
+                    max: voxel,
+                })
+            }
+        }
+        self.filled.insert(voxel)
+    }
+
+    pub fn contains(&self, voxel: Voxel) -> bool {
+        self.filled.contains(&voxel)
+    }
+
+    pub fn len(&self) -> usize {
+        self.filled.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.filled.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Voxel> + '_ {
+        self.filled.iter().copied()
+    }
+
+    /// Smallest box containing all filled voxels, `None` while the grid is empty.
+    pub fn bounds(&self) -> Option<BoundingBox> {
+        self.bounds
+    }
+
+    /// Empty voxels reachable from `start` through faces without leaving `within`.
+    pub fn flood_fill(&self, start: Voxel, within: BoundingBox) -> HashSet<Voxel> {
+        let mut reached = HashSet::new();
+        if self.contains(start) || !within.contains(start) {
+            return reached;
+        }
+        reached.insert(start);
+        let mut todo = vec![start];
+        while let Some(voxel) = todo.pop() {
+            for neighbor in voxel.neighbors() {
+                if within.contains(neighbor) && !self.contains(neighbor) && reached.insert(neighbor)
+                {
+                    todo.push(neighbor);
+                }
+            }
+        }
+        reached
+    }
+
+    /// Empty voxels connected to the outside, within the bounding box grown by one so the
+    /// fill can wrap around the whole shape.
+    pub fn exterior(&self) -> HashSet<Voxel> {
+        match self.bounds {
+            Some(bounds) => {
+                let within = bounds.expand(1);
+                self.flood_fill(within.min, within)
+            }
+            None => HashSet::new(),
+        }
+    }
+
+    /// Number of faces of filled voxels that don't touch another filled voxel, including
+    /// the faces of enclosed air pockets.
+    pub fn surface_area(&self) -> usize {
+        self.iter()
+            .flat_map(Voxel::neighbors)
+            .filter(|n| !self.contains(*n))
+            .count()
+    }
+
+    /// Number of faces of filled voxels that can be reached from the outside.
+    pub fn exterior_surface_area(&self) -> usize {
+        let exterior = self.exterior();
+        self.iter()
+            .flat_map(Voxel::neighbors)
+            .filter(|n| exterior.contains(n))
+            .count()
+    }
+}
+
+impl FromIterator<Voxel> for VoxelGrid {
+    fn from_iter<T: IntoIterator<Item = Voxel>>(iter: T) -> Self {
+        let mut grid = VoxelGrid::new();
+        for voxel in iter {
+            grid.insert(voxel);
+        }
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 3x3x3 cube with its center missing.
+    fn hollow_cube() -> VoxelGrid {
+        (0..27)
+            .map(|i| Voxel::new(i % 3, i / 3 % 3, i / 9))
+            .filter(|&v| v != Voxel::new(1, 1, 1))
+            .collect()
+    }
+
+    #[test]
+    fn test_bounds() {
+        let mut grid = VoxelGrid::new();
+        assert_eq!(grid.bounds(), None);
+        grid.insert(Voxel::new(1, -2, 3));
+        grid.insert(Voxel::new(-1, 2, 0));
+        assert_eq!(
+            grid.bounds(),
+            Some(BoundingBox {
+                min: Voxel::new(-1, -2, 0),
+                max: Voxel::new(1, 2, 3),
+            })
+        );
+    }
+
+    #[test]
+    fn test_surface_area() {
+        let grid: VoxelGrid = [Voxel::new(1, 1, 1), Voxel::new(2, 1, 1)]
+            .into_iter()
+            .collect();
+        assert_eq!(grid.surface_area(), 10);
+        assert_eq!(grid.exterior_surface_area(), 10);
+    }
+
+    #[test]
+    fn test_air_pocket() {
+        let grid = hollow_cube();
+        assert_eq!(grid.surface_area(), 54 + 6);
+        assert_eq!(grid.exterior_surface_area(), 54);
+        assert!(!grid.exterior().contains(&Voxel::new(1, 1, 1)));
+        assert_eq!(grid.exterior().len(), 5 * 5 * 5 - 27);
+    }
+}
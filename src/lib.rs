@@ -4,47 +4,405 @@
  * Prefer `./helpers.rs` if you want to extract code from your solutions.
  */
 use std::env;
+use std::error::Error;
+use std::fmt::{Debug, Display};
 use std::fs;
+use std::num::ParseIntError;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
 
+pub mod calendar;
+pub mod config;
+pub mod days;
+pub mod differential;
+pub mod fuzz;
+pub mod generate;
 pub mod helpers;
+pub mod memory;
+pub mod registry;
+pub mod span;
+pub mod vault;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-#[macro_export]
-macro_rules! solve {
-    ($part:expr, $solver:ident, $input:expr) => {{
-        use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
-        use std::fmt::Display;
-        use std::time::Instant;
-
-        fn print_result<T: Display>(func: impl FnOnce(&str) -> Option<T>, input: &str) {
-            let timer = Instant::now();
-            let result = func(input);
-            let elapsed = timer.elapsed();
-            match result {
-                Some(result) => {
-                    println!(
-                        "{} {}(elapsed: {:.2?}){}",
-                        result, ANSI_ITALIC, elapsed, ANSI_RESET
-                    );
-                }
+/// A day's solution, split into parsing the input and solving each part.
+///
+/// The input is parsed once and shared by both parts, so [`solve!`] can time all three
+/// phases separately. `Parsed` may borrow from the input, e.g. names as `&'input str`.
+pub trait Solution {
+    type Parsed<'input>;
+    type PartOne: Display;
+    type PartTwo: Display;
+
+    /// Puzzle constants that differ between the example and the real input.
+    const PARAMS: &'static [Param] = &[];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>>;
+    fn part_one(parsed: &Self::Parsed<'_>, params: &Params) -> Option<Self::PartOne>;
+    fn part_two(parsed: &Self::Parsed<'_>, params: &Params) -> Option<Self::PartTwo>;
+}
+
+/// A named puzzle constant, e.g. the row to inspect, with one value per kind of input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    pub example: i64,
+    pub real: i64,
+}
+
+impl Param {
+    pub const fn new(name: &'static str, example: i64, real: i64) -> Self {
+        Self {
+            name,
+            example,
+            real,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputKind {
+    Example,
+    Real,
+}
+
+/// Values of a day's [`Param`]s for one kind of input.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params {
+    values: Vec<(&'static str, i64)>,
+}
+
+impl Params {
+    pub fn new(declared: &[Param], kind: InputKind) -> Self {
+        let values = declared
+            .iter()
+            .map(|p| match kind {
+                InputKind::Example => (p.name, p.example),
+                InputKind::Real => (p.name, p.real),
+            })
+            .collect();
+        Self { values }
+    }
+
+    /// Values for the real input, overridden by any `--param name=value` arguments.
+    pub fn from_env(declared: &[Param]) -> std::result::Result<Self, String> {
+        let overrides = parse_param_overrides(env::args().skip(1))?;
+        Params::new(declared, InputKind::Real).with_overrides(&overrides)
+    }
+
+    pub fn with_overrides(
+        mut self,
+        overrides: &[(String, i64)],
+    ) -> std::result::Result<Self, String> {
+        for (name, value) in overrides {
+            match self.values.iter_mut().find(|(n, _)| n == name) {
+                Some((_, v)) => *v = *value,
                 None => {
-                    println!("not solved.")
+                    let known = self.values.iter().map(|(n, _)| *n).collect::<Vec<_>>();
+                    return Err(format!(
+                        "Unknown parameter `{name}`, expected one of: [{}]",
+                        known.join(", ")
+                    ));
                 }
             }
         }
+        Ok(self)
+    }
+
+    /// Panics if the day does not declare a parameter called `name`.
+    pub fn get(&self, name: &str) -> i64 {
+        match self.values.iter().find(|(n, _)| *n == name) {
+            Some(&(_, value)) => value,
+            None => panic!("Parameter `{name}` is not declared in `Solution::PARAMS`"),
+        }
+    }
+}
+
+fn parse_param_overrides(
+    args: impl Iterator<Item = String>,
+) -> std::result::Result<Vec<(String, i64)>, String> {
+    let mut overrides = Vec::new();
+    let mut args = args.peekable();
+    while let Some(arg) = args.next() {
+        let assignment = if arg == "--param" {
+            args.next()
+                .ok_or_else(|| "Missing value for `--param`, expected name=value".to_string())?
+        } else if let Some(assignment) = arg.strip_prefix("--param=") {
+            assignment.to_string()
+        } else {
+            continue;
+        };
+        let (name, value) = assignment
+            .split_once('=')
+            .ok_or_else(|| format!("Invalid parameter `{assignment}`, expected name=value"))?;
+        let value = value
+            .parse()
+            .map_err(|_| format!("Invalid value for parameter `{name}`: {value}"))?;
+        overrides.push((name.to_string(), value));
+    }
+    Ok(overrides)
+}
+
+/// Error returned by [`Solution::parse`] for malformed input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError(pub String);
+
+impl ParseError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self(message.into())
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "could not parse input: {}", self.0)
+    }
+}
+
+impl Error for ParseError {}
 
-        println!("🎄 {}Part {}{} 🎄", ANSI_BOLD, $part, ANSI_RESET);
-        print_result($solver, $input);
+impl From<ParseIntError> for ParseError {
+    fn from(err: ParseIntError) -> Self {
+        Self(err.to_string())
+    }
+}
+
+impl<E: Debug> From<nom::Err<E>> for ParseError {
+    fn from(err: nom::Err<E>) -> Self {
+        Self(format!("{err:?}"))
+    }
+}
+
+pub type Result<T, E = ParseError> = std::result::Result<T, E>;
+
+#[macro_export]
+macro_rules! solve {
+    ($solution:ty, $input:expr) => {{
+        match $crate::Params::from_env(<$solution as $crate::Solution>::PARAMS) {
+            Ok(params) => $crate::run::<$solution>($input, &params),
+            Err(err) => {
+                eprintln!("{err}");
+                std::process::exit(1);
+            }
+        }
     }};
 }
 
+/// Parses `input` once, then solves both parts, printing each result with its timing and,
+/// with the `alloc-stats` feature, its heap usage. Spans recorded with [`aoc_span!`] are
+/// printed as a tree to stderr after each phase. With [`REPORT_VAR`] set, each phase is
+/// followed by its exact [`PhaseReport`].
+pub fn run<S: Solution>(input: &str, params: &Params) {
+    fn measure<T>(f: impl FnOnce() -> T) -> (T, PhaseReport, String) {
+        span::take();
+        let timer = Instant::now();
+        let (result, mem) = memory::measure(f);
+        let elapsed = timer.elapsed();
+        let spans = span::report(&span::take(), elapsed);
+        (result, PhaseReport { elapsed, mem }, spans)
+    }
+
+    fn print_phase(result: Option<&dyn Display>, phase: PhaseReport, spans: String) {
+        let elapsed = phase.elapsed;
+        let stats = match phase.mem {
+            Some(mem) => format!("(elapsed: {elapsed:.2?}, {mem})"),
+            None => format!("(elapsed: {elapsed:.2?})"),
+        };
+        match result {
+            Some(result) => {
+                println!("{result} {ANSI_ITALIC}{stats}{ANSI_RESET}");
+            }
+            None => {
+                println!("not solved.")
+            }
+        }
+        if env::var_os(REPORT_VAR).is_some() {
+            println!("{phase}");
+        }
+        eprint!("{spans}");
+    }
+
+    println!("🎄 {ANSI_BOLD}Parse{ANSI_RESET} 🎄");
+    let (parsed, phase, spans) = measure(|| S::parse(input));
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            eprintln!("{PARSE_FAILURE} {err}");
+            std::process::exit(1);
+        }
+    };
+    print_phase(Some(&"ok"), phase, spans);
+
+    println!("🎄 {ANSI_BOLD}Part 1{ANSI_RESET} 🎄");
+    let (result, phase, spans) = measure(|| S::part_one(&parsed, params));
+    print_phase(result.as_ref().map(|r| r as &dyn Display), phase, spans);
+
+    println!("🎄 {ANSI_BOLD}Part 2{ANSI_RESET} 🎄");
+    let (result, phase, spans) = measure(|| S::part_two(&parsed, params));
+    print_phase(result.as_ref().map(|r| r as &dyn Display), phase, spans);
+}
+
+/// Solves part one of the example `input` with the example parameters, for tests.
+pub fn example_part_one<S: Solution>(input: &str) -> Option<S::PartOne> {
+    let parsed = S::parse(input).expect("could not parse example");
+    S::part_one(&parsed, &Params::new(S::PARAMS, InputKind::Example))
+}
+
+/// Solves part two of the example `input` with the example parameters, for tests.
+pub fn example_part_two<S: Solution>(input: &str) -> Option<S::PartTwo> {
+    let parsed = S::parse(input).expect("could not parse example");
+    S::part_two(&parsed, &Params::new(S::PARAMS, InputKind::Example))
+}
+
+/// Generates a `#[cfg(test)] mod examples` checking a day's answers for its example inputs.
+///
+/// Each example reads its input from `day = 7` (`examples/07.txt`), `file = "07-2"`
+/// (`examples/07-2.txt`) or `input = "..."`, and checks any of `part_one` / `part_two`.
+/// Attributes on a part are forwarded to its test, e.g. `#[ignore] part_two = Some(42)`
+/// for a known answer the solution can't produce yet.
+///
+/// ```ignore
+/// aoc_test!(Day07, day = 7, part_one = Some(95437), part_two = Some(24933642));
+///
+/// aoc_test!(
+///     Day09,
+///     example { day = 9, part_one = Some(13), part_two = Some(1) },
+///     larger { file = "09-2", #[ignore] part_two = Some(36) },
+/// );
+/// ```
+#[macro_export]
+macro_rules! aoc_test {
+    ($solution:ty, $source:ident = $value:expr $(, $(#[$attr:meta])* $part:ident = $expected:expr)* $(,)?) => {
+        #[cfg(test)]
+        mod examples {
+            use super::*;
+
+            $($crate::aoc_test!(@part $solution, $source = $value, $(#[$attr])* $part = $expected);)*
+        }
+    };
+    ($solution:ty $(, $name:ident { $source:ident = $value:expr $(, $(#[$attr:meta])* $part:ident = $expected:expr)* $(,)? })+ $(,)?) => {
+        #[cfg(test)]
+        mod examples {
+            use super::*;
+
+            $(
+                mod $name {
+                    use super::*;
+
+                    $($crate::aoc_test!(@part $solution, $source = $value, $(#[$attr])* $part = $expected);)*
+                }
+            )+
+        }
+    };
+    (@part $solution:ty, $source:ident = $value:expr, $(#[$attr:meta])* part_one = $expected:expr) => {
+        #[test]
+        $(#[$attr])*
+        fn part_one() {
+            let input = $crate::aoc_test!(@input $source = $value);
+            assert_eq!($crate::example_part_one::<$solution>(&input), $expected);
+        }
+    };
+    (@part $solution:ty, $source:ident = $value:expr, $(#[$attr:meta])* part_two = $expected:expr) => {
+        #[test]
+        $(#[$attr])*
+        fn part_two() {
+            let input = $crate::aoc_test!(@input $source = $value);
+            assert_eq!($crate::example_part_two::<$solution>(&input), $expected);
+        }
+    };
+    (@input day = $day:expr) => {
+        $crate::read_file("examples", $day)
+    };
+    (@input file = $name:expr) => {
+        $crate::read_named_file("examples", $name)
+    };
+    (@input input = $input:expr) => {
+        String::from($input)
+    };
+}
+
+/// Verbosity of [`aoc_debug!`] and [`aoc_trace!`] output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Off,
+    Debug,
+    Trace,
+}
+
+/// Reads the log level from `AOC_LOG` (`debug` or `trace`) and the `-v` / `-vv` flags.
+/// Whichever is more verbose wins.
+pub fn log_level() -> LogLevel {
+    static LEVEL: OnceLock<LogLevel> = OnceLock::new();
+    *LEVEL.get_or_init(|| {
+        let env = env::var("AOC_LOG").ok();
+        parse_log_level(env.as_deref(), env::args().skip(1))
+    })
+}
+
+fn parse_log_level(env: Option<&str>, args: impl Iterator<Item = String>) -> LogLevel {
+    let from_env = match env.map(|v| v.trim().to_lowercase()).as_deref() {
+        Some("trace") | Some("2") => LogLevel::Trace,
+        Some("debug") | Some("1") => LogLevel::Debug,
+        _ => LogLevel::Off,
+    };
+    let verbosity: usize = args
+        .map(|arg| match arg.as_str() {
+            "--verbose" => 1,
+            a if a.len() > 1 && a.starts_with('-') && a[1..].chars().all(|c| c == 'v') => {
+                a.len() - 1
+            }
+            _ => 0,
+        })
+        .sum();
+    let from_args = match verbosity {
+        0 => LogLevel::Off,
+        1 => LogLevel::Debug,
+        _ => LogLevel::Trace,
+    };
+    from_env.max(from_args)
+}
+
+/// Prints to stderr if the log level is at least `debug`.
+/// Compiles to nothing in release builds, so it never affects benchmark timings.
+#[macro_export]
+macro_rules! aoc_debug {
+    ($($arg:tt)*) => {
+        if cfg!(debug_assertions) && $crate::log_level() >= $crate::LogLevel::Debug {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+/// Like [`aoc_debug!`], for very chatty output such as per-step simulation state.
+#[macro_export]
+macro_rules! aoc_trace {
+    ($($arg:tt)*) => {
+        if cfg!(debug_assertions) && $crate::log_level() >= $crate::LogLevel::Trace {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
 pub fn read_file(folder: &str, day: u8) -> String {
+    read_named_file(folder, &format!("{day:02}"))
+}
+
+/// Reads `{folder}/{name}.txt`, e.g. a second example saved as `examples/09-2.txt`.
+/// `inputs`, `examples` and `answers` are looked up in `aoc.toml`, other folders in `src`.
+/// A missing input is decrypted from the vault if it has a copy.
+pub fn read_named_file(folder: &str, name: &str) -> String {
     let cwd = env::current_dir().unwrap();
 
-    let filepath = cwd.join("src").join(folder).join(format!("{day:02}.txt"));
+    let config = config::get();
+    let filepath = cwd.join(config.folder(folder)).join(format!("{name}.txt"));
+
+    // a fresh clone has no inputs, only their encrypted copies from `cargo vault`
+    if folder == "inputs" && !filepath.exists() && cwd.join(config.vault_path(name)).exists() {
+        return vault::read(&cwd, config, name)
+            .unwrap_or_else(|e| panic!("could not decrypt input file: {e}"));
+    }
 
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
@@ -54,27 +412,199 @@ fn parse_time(val: &str, postfix: &str) -> f64 {
     val.split(postfix).next().unwrap().parse().unwrap()
 }
 
+/// Execution time in milliseconds reported on a single line of output, or zero.
+fn parse_line_time(line: &str) -> f64 {
+    if !line.contains("elapsed:") {
+        return 0_f64;
+    }
+    let timing = line.split("(elapsed: ").last().unwrap();
+    // the timing ends the parentheses, or is followed by heap usage after a comma.
+    let timing = timing.split([')', ',']).next().unwrap();
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    if timing.ends_with("ns") {
+        0_f64 // range below rounding precision.
+    } else if timing.ends_with("µs") {
+        parse_time(timing, "µs") / 1000_f64
+    } else if timing.ends_with("ms") {
+        parse_time(timing, "ms")
+    } else if timing.ends_with('s') {
+        parse_time(timing, "s") * 1000_f64
+    } else {
+        0_f64
+    }
+}
+
 pub fn parse_exec_time(output: &str) -> f64 {
-    output.lines().fold(0_f64, |acc, l| {
-        if !l.contains("elapsed:") {
-            acc
-        } else {
-            let timing = l.split("(elapsed: ").last().unwrap();
-            // use `contains` istd. of `ends_with`: string may contain ANSI escape sequences.
-            // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-            if timing.contains("ns)") {
-                acc // range below rounding precision.
-            } else if timing.contains("µs)") {
-                acc + parse_time(timing, "µs") / 1000_f64
-            } else if timing.contains("ms)") {
-                acc + parse_time(timing, "ms")
-            } else if timing.contains("s)") {
-                acc + parse_time(timing, "s") * 1000_f64
-            } else {
-                acc
-            }
+    output.lines().map(parse_line_time).sum()
+}
+
+/// Set in the environment of a day's binary to have [`run`] print a [`PhaseReport`] after
+/// each phase. The timings and heap usage meant for people are rounded, these are exact.
+pub const REPORT_VAR: &str = "AOC_REPORT";
+
+/// Starts the line of a [`PhaseReport`].
+pub const REPORT_PREFIX: &str = "aoc-report:";
+
+/// The exact timing and heap usage of one phase, printed as a single line such as
+/// `aoc-report: elapsed_ns=1500 allocated=1536 allocations=12 peak=800`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhaseReport {
+    pub elapsed: Duration,
+    pub mem: Option<memory::MemStats>,
+}
+
+impl Display for PhaseReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{REPORT_PREFIX} elapsed_ns={}", self.elapsed.as_nanos())?;
+        if let Some(mem) = self.mem {
+            write!(
+                f,
+                " allocated={} allocations={} peak={}",
+                mem.allocated, mem.allocations, mem.peak
+            )?;
         }
-    })
+        Ok(())
+    }
+}
+
+impl PhaseReport {
+    /// Reads back a line printed by [`run`], `None` for any other line.
+    pub fn parse(line: &str) -> Option<Self> {
+        let fields = line.strip_prefix(REPORT_PREFIX)?;
+        let mut values = [None; 4];
+        for field in fields.split_whitespace() {
+            let (key, value) = field.split_once('=')?;
+            let index = ["elapsed_ns", "allocated", "allocations", "peak"]
+                .iter()
+                .position(|k| *k == key)?;
+            values[index] = Some(value.parse::<u64>().ok()?);
+        }
+        let mem = match values[1..] {
+            [Some(allocated), Some(allocations), Some(peak)] => Some(memory::MemStats {
+                allocated,
+                allocations,
+                peak,
+            }),
+            [None, None, None] => None,
+            _ => return None,
+        };
+        Some(Self {
+            elapsed: Duration::from_nanos(values[0]?),
+            mem,
+        })
+    }
+}
+
+/// The reports of the parse step and both parts in the output of [`solve!`] run with
+/// [`REPORT_VAR`] set, `None` for a phase that wasn't reached.
+pub fn parse_reports(output: &str) -> [Option<PhaseReport>; 3] {
+    let mut reports = [None; 3];
+    let mut section = None;
+    for line in output.lines() {
+        if line.starts_with('🎄') {
+            section = ["Parse", "Part 1", "Part 2"]
+                .iter()
+                .position(|name| line.contains(name));
+        } else if let (Some(section), Some(report)) = (section, PhaseReport::parse(line)) {
+            reports[section] = Some(report);
+        }
+    }
+    reports
+}
+
+/// The output of [`solve!`] without its [`PhaseReport`] lines, as shown to people.
+pub fn without_reports(output: &str) -> String {
+    output
+        .lines()
+        .filter(|line| !line.starts_with(REPORT_PREFIX))
+        .map(|line| format!("{line}\n"))
+        .collect()
+}
+
+/// Timings of the parse step and both parts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExecTimes {
+    pub parse: Duration,
+    pub part_one: Duration,
+    pub part_two: Duration,
+}
+
+impl ExecTimes {
+    pub fn total(&self) -> Duration {
+        self.parse + self.part_one + self.part_two
+    }
+}
+
+impl std::ops::Add for ExecTimes {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            parse: self.parse + other.parse,
+            part_one: self.part_one + other.part_one,
+            part_two: self.part_two + other.part_two,
+        }
+    }
+}
+
+impl std::iter::Sum for ExecTimes {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |a, b| a + b)
+    }
+}
+
+/// Exact timings read back from the output of [`solve!`] run with [`REPORT_VAR`] set.
+pub fn parse_exec_times(output: &str) -> ExecTimes {
+    let [parse, part_one, part_two] =
+        parse_reports(output).map(|report| report.map_or(Duration::ZERO, |r| r.elapsed));
+    ExecTimes {
+        parse,
+        part_one,
+        part_two,
+    }
+}
+
+/// Exact heap usage of the parse step and both parts read back from the output of [`solve!`]
+/// run with [`REPORT_VAR`] set, `None` for a phase that did not report any, e.g. without the
+/// `alloc-stats` feature.
+pub fn parse_mem_stats(output: &str) -> [Option<memory::MemStats>; 3] {
+    parse_reports(output).map(|report| report.and_then(|r| r.mem))
+}
+
+/// Answers of both parts read back from the output of [`solve!`], `None` if not solved.
+pub fn parse_answers(output: &str) -> [Option<String>; 2] {
+    let mut answers = [None, None];
+    let mut part = None;
+    for line in output.lines() {
+        if line.starts_with('🎄') {
+            part = ["Part 1", "Part 2"]
+                .iter()
+                .position(|name| line.contains(name));
+        } else if let (Some(part), false) = (part, line.starts_with(REPORT_PREFIX)) {
+            answers[part] = line
+                .split_once(&format!(" {ANSI_ITALIC}(elapsed: "))
+                .map(|(answer, _)| answer.to_string());
+        }
+    }
+    answers
+}
+
+/// Starts the error [`run`] prints to stderr before exiting when the input can't be parsed.
+pub const PARSE_FAILURE: &str = "Failed to parse input:";
+
+/// Why a day's binary failed, read back from its stderr: the parse error printed by [`run`],
+/// or the message of a panic.
+pub fn parse_failure(stderr: &str) -> Option<String> {
+    let mut lines = stderr.lines();
+    while let Some(line) = lines.next() {
+        if line.starts_with(PARSE_FAILURE) {
+            return Some(line.to_string());
+        }
+        if line.contains("panicked at") {
+            return lines.next().map(str::to_string);
+        }
+    }
+    None
 }
 
 /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -121,4 +651,185 @@ mod tests {
             10400.50_f64
         );
     }
+
+    #[test]
+    fn test_phase_report() {
+        let report = PhaseReport {
+            elapsed: Duration::from_nanos(74),
+            mem: Some(memory::MemStats {
+                allocated: 1537,
+                allocations: 3,
+                peak: 1025,
+            }),
+        };
+        let line = report.to_string();
+        assert_eq!(
+            line,
+            "aoc-report: elapsed_ns=74 allocated=1537 allocations=3 peak=1025"
+        );
+        assert_eq!(PhaseReport::parse(&line), Some(report));
+        let report = PhaseReport {
+            elapsed: Duration::from_secs(10),
+            mem: None,
+        };
+        assert_eq!(PhaseReport::parse(&report.to_string()), Some(report));
+        assert_eq!(PhaseReport::parse("aoc-report: elapsed_ns=1 peak=2"), None);
+        assert_eq!(PhaseReport::parse("0 (elapsed: 1.50ms)"), None);
+    }
+
+    #[test]
+    fn test_parse_exec_times() {
+        let output = format!(
+            "🎄 {ANSI_BOLD}Parse{ANSI_RESET} 🎄\nok (elapsed: 500.00ns)\n{REPORT_PREFIX} elapsed_ns=499\n🎄 Part 1 🎄\n0 {ANSI_ITALIC}(elapsed: 1.50ms){ANSI_RESET}\n{REPORT_PREFIX} elapsed_ns=1501234\n🎄 Part 2 🎄\nnot solved."
+        );
+        let times = parse_exec_times(&output);
+        assert_eq!(times.parse, Duration::from_nanos(499));
+        assert_eq!(times.part_one, Duration::from_nanos(1_501_234));
+        assert_eq!(times.part_two, Duration::ZERO);
+        assert_eq!(times.total(), Duration::from_nanos(1_501_733));
+        assert_eq!(parse_answers(&output), [Some("0".to_string()), None]);
+        assert!(!without_reports(&output).contains(REPORT_PREFIX));
+    }
+
+    #[test]
+    fn test_parse_exec_times_with_mem_stats() {
+        let output = format!(
+            "🎄 Parse 🎄\nok {ANSI_ITALIC}(elapsed: 500µs, allocated: 2.00 KiB in 3 allocations, peak: 1.00 KiB){ANSI_RESET}\n{REPORT_PREFIX} elapsed_ns=500000 allocated=2049 allocations=3 peak=1023\n🎄 Part 1 🎄\n0 {ANSI_ITALIC}(elapsed: 1.50ms, allocated: 0 B in 0 allocations, peak: 0 B){ANSI_RESET}\n{REPORT_PREFIX} elapsed_ns=1500000 allocated=0 allocations=0 peak=0\n🎄 Part 2 🎄\nnot solved."
+        );
+        let stats = parse_mem_stats(&output);
+        assert_eq!(
+            stats[0],
+            Some(memory::MemStats {
+                allocated: 2049,
+                allocations: 3,
+                peak: 1023
+            })
+        );
+        assert_eq!(stats[1], Some(memory::MemStats::default()));
+        assert_eq!(stats[2], None);
+        assert_eq!(parse_answers(&output)[0], Some("0".to_string()));
+    }
+
+    #[test]
+    fn test_params() {
+        const DECLARED: &[Param] = &[
+            Param::new("row", 10, 2_000_000),
+            Param::new("bound", 20, 4_000_000),
+        ];
+
+        let example = Params::new(DECLARED, InputKind::Example);
+        assert_eq!(example.get("row"), 10);
+        assert_eq!(example.get("bound"), 20);
+
+        let real = Params::new(DECLARED, InputKind::Real);
+        assert_eq!(real.get("row"), 2_000_000);
+        let overridden = real.with_overrides(&[("row".to_string(), 11)]).unwrap();
+        assert_eq!(overridden.get("row"), 11);
+        assert_eq!(overridden.get("bound"), 4_000_000);
+        assert!(overridden
+            .with_overrides(&[("col".to_string(), 1)])
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_param_overrides() {
+        let args = |a: &[&str]| {
+            a.iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>()
+                .into_iter()
+        };
+
+        assert_eq!(
+            parse_param_overrides(args(&["-v", "--param", "row=10", "--param=bound=-3"])),
+            Ok(vec![("row".to_string(), 10), ("bound".to_string(), -3)])
+        );
+        assert!(parse_param_overrides(args(&["--param"])).is_err());
+        assert!(parse_param_overrides(args(&["--param", "row"])).is_err());
+        assert!(parse_param_overrides(args(&["--param", "row=ten"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_answers() {
+        let mut output = String::new();
+        output += &format!(
+            "🎄 {ANSI_BOLD}Parse{ANSI_RESET} 🎄\nok {ANSI_ITALIC}(elapsed: 1.00µs){ANSI_RESET}\n"
+        );
+        output += &format!("🎄 {ANSI_BOLD}Part 1{ANSI_RESET} 🎄\n95437 {ANSI_ITALIC}(elapsed: 2.00µs){ANSI_RESET}\n");
+        output += &format!("🎄 {ANSI_BOLD}Part 2{ANSI_RESET} 🎄\nnot solved.\n");
+        assert_eq!(parse_answers(&output), [Some("95437".to_string()), None]);
+    }
+
+    #[test]
+    fn test_parse_failure() {
+        let stderr = "   Compiling advent_of_code\n     Running `target/debug/07`\nFailed to parse input: invalid digit found in string\n";
+        assert_eq!(
+            parse_failure(stderr).as_deref(),
+            Some("Failed to parse input: invalid digit found in string")
+        );
+        let stderr = "\nthread 'main' panicked at src/bin/24.rs:80:9:\nnot yet implemented\nnote: run with `RUST_BACKTRACE=1`\n";
+        assert_eq!(
+            parse_failure(stderr).as_deref(),
+            Some("not yet implemented")
+        );
+        assert_eq!(parse_failure("     Running `target/debug/07`\n"), None);
+    }
+
+    /// Sums the numbers on each line, part two multiplies them by the `factor` parameter.
+    struct Numbers;
+
+    impl Solution for Numbers {
+        type Parsed<'a> = Vec<u32>;
+        type PartOne = u32;
+        type PartTwo = u32;
+
+        const PARAMS: &'static [Param] = &[Param::new("factor", 2, 10)];
+
+        fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+            Ok(input
+                .lines()
+                .map(str::parse)
+                .collect::<std::result::Result<_, _>>()?)
+        }
+
+        fn part_one(numbers: &Self::Parsed<'_>, _: &Params) -> Option<u32> {
+            Some(numbers.iter().sum())
+        }
+
+        fn part_two(numbers: &Self::Parsed<'_>, params: &Params) -> Option<u32> {
+            Some(numbers.iter().sum::<u32>() * params.get("factor") as u32)
+        }
+    }
+
+    crate::aoc_test!(
+        Numbers,
+        small { input = "1\n2", part_one = Some(3), part_two = Some(6) },
+        empty { input = "", part_one = Some(0) },
+        broken { input = "1\nx", #[should_panic] part_one = None::<u32> },
+    );
+
+    #[test]
+    fn test_parse_log_level() {
+        let args = |a: &[&str]| {
+            a.iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>()
+                .into_iter()
+        };
+
+        assert_eq!(parse_log_level(None, args(&[])), LogLevel::Off);
+        assert_eq!(parse_log_level(Some("debug"), args(&[])), LogLevel::Debug);
+        assert_eq!(parse_log_level(Some("TRACE"), args(&[])), LogLevel::Trace);
+        assert_eq!(parse_log_level(None, args(&["-v"])), LogLevel::Debug);
+        assert_eq!(parse_log_level(None, args(&["-vv"])), LogLevel::Trace);
+        assert_eq!(
+            parse_log_level(None, args(&["-v", "--verbose"])),
+            LogLevel::Trace
+        );
+        assert_eq!(
+            parse_log_level(Some("trace"), args(&["-v"])),
+            LogLevel::Trace
+        );
+        assert_eq!(parse_log_level(None, args(&["-x", "v"])), LogLevel::Off);
+    }
 }
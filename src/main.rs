@@ -2,44 +2,182 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
-use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
-use std::process::Command;
+use advent_of_code::days::DAYS;
+use advent_of_code::memory::MemStats;
+use advent_of_code::{ExecTimes, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, REPORT_VAR};
+use std::env;
+use std::process::{self, Command};
+use std::time::Duration;
+
+/// What a single day printed, read back from its exact reports.
+struct DayReport {
+    day: u8,
+    times: ExecTimes,
+    mem: [Option<MemStats>; 3],
+    answers: [Option<String>; 2],
+}
 
 fn main() {
-    let total: f64 = (1..=25)
+    let json = env::args().skip(1).any(|arg| arg == "--json");
+
+    let mut args = vec!["run", "--release"];
+    if cfg!(feature = "alloc-stats") {
+        args.extend(["--features", "alloc-stats"]);
+    }
+
+    // `None` for days without a solution, an error for days whose binary failed
+    let reports: Vec<Option<Result<DayReport, String>>> = (1..=25)
         .map(|day| {
-            let day = format!("{day:02}");
-
-            let cmd = Command::new("cargo")
-                .args(["run", "--release", "--bin", &day])
-                .output()
-                .unwrap();
-
-            println!("----------");
-            println!("{ANSI_BOLD}| Day {day} |{ANSI_RESET}");
-            println!("----------");
-
-            let output = String::from_utf8(cmd.stdout).unwrap();
-            let is_empty = output.is_empty();
-
-            println!(
-                "{}",
-                if is_empty {
-                    "Not solved."
-                } else {
-                    output.trim()
+            let day_padded = format!("{day:02}");
+
+            // days missing from the solution registry have no binary to run
+            let run = DAYS.contains(&day).then(|| {
+                Command::new("cargo")
+                    .args(&args)
+                    .args(["--bin", &day_padded])
+                    .env(REPORT_VAR, "1")
+                    .output()
+                    .unwrap()
+            });
+            let output = run
+                .as_ref()
+                .map(|run| String::from_utf8_lossy(&run.stdout).into_owned())
+                .unwrap_or_default();
+            let failure = run.as_ref().filter(|run| !run.status.success()).map(|run| {
+                advent_of_code::parse_failure(&String::from_utf8_lossy(&run.stderr))
+                    .unwrap_or_else(|| format!("exited with {}", run.status))
+            });
+
+            if !json {
+                println!("----------");
+                println!("{ANSI_BOLD}| Day {day_padded} |{ANSI_RESET}");
+                println!("----------");
+                if !output.is_empty() {
+                    println!("{}", advent_of_code::without_reports(&output).trim());
+                }
+                match &failure {
+                    Some(failure) => println!("Failed: {failure}"),
+                    None if output.is_empty() => println!("Not solved."),
+                    None => {}
                 }
-            );
+            }
 
-            if is_empty {
-                0_f64
-            } else {
-                advent_of_code::parse_exec_time(&output)
+            if let Some(failure) = failure {
+                return Some(Err(failure));
             }
+            (!output.is_empty()).then(|| {
+                Ok(DayReport {
+                    day,
+                    times: advent_of_code::parse_exec_times(&output),
+                    mem: advent_of_code::parse_mem_stats(&output),
+                    answers: advent_of_code::parse_answers(&output),
+                })
+            })
         })
-        .sum();
+        .collect();
+
+    let solved = || reports.iter().flatten().flatten();
+    let total: ExecTimes = solved().map(|r| r.times).sum();
+    let total_mem: Option<MemStats> = solved()
+        .flat_map(|r| r.mem.into_iter().flatten())
+        .reduce(|a, b| a + b);
+
+    let failed = reports.iter().any(|report| matches!(report, Some(Err(_))));
+    if json {
+        println!("{}", to_json(&reports, &total, total_mem));
+        if failed {
+            process::exit(1);
+        }
+        return;
+    }
 
     println!(
-        "{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total:.2}ms{ANSI_RESET}"
+        "{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{:.2?} (parse: {:.2?}, part 1: {:.2?}, part 2: {:.2?}){ANSI_RESET}",
+        total.total(),
+        total.parse,
+        total.part_one,
+        total.part_two
     );
+    if let Some(total_mem) = total_mem {
+        println!("{ANSI_BOLD}Memory:{ANSI_RESET} {ANSI_ITALIC}{total_mem}{ANSI_RESET}");
+    }
+    if failed {
+        process::exit(1);
+    }
+}
+
+/// All reports as one JSON document, e.g. for `cargo all -- --json > timings.json`.
+/// Times are exact in nanoseconds and heap usage in bytes; heap usage is `null` without the
+/// `alloc-stats` feature. A day whose binary failed has an `error` instead.
+fn to_json(
+    reports: &[Option<Result<DayReport, String>>],
+    total: &ExecTimes,
+    total_mem: Option<MemStats>,
+) -> String {
+    let days = reports
+        .iter()
+        .enumerate()
+        .map(|(i, report)| match report {
+            None => format!(r#"{{"day":{},"solved":false}}"#, i + 1),
+            Some(Err(failure)) => format!(
+                r#"{{"day":{},"solved":false,"error":{}}}"#,
+                i + 1,
+                json_string(failure)
+            ),
+            Some(Ok(r)) => format!(
+                r#"{{"day":{},"solved":true,"parse":{},"part_one":{},"part_two":{}}}"#,
+                r.day,
+                phase_json(None, r.times.parse, r.mem[0]),
+                phase_json(Some(&r.answers[0]), r.times.part_one, r.mem[1]),
+                phase_json(Some(&r.answers[1]), r.times.part_two, r.mem[2]),
+            ),
+        })
+        .collect::<Vec<_>>();
+    format!(
+        r#"{{"days":[{}],"total":{{"elapsed_ns":{},"parse_ns":{},"part_one_ns":{},"part_two_ns":{},"memory":{}}}}}"#,
+        days.join(","),
+        total.total().as_nanos(),
+        total.parse.as_nanos(),
+        total.part_one.as_nanos(),
+        total.part_two.as_nanos(),
+        mem_json(total_mem)
+    )
+}
+
+fn phase_json(answer: Option<&Option<String>>, elapsed: Duration, mem: Option<MemStats>) -> String {
+    let answer = match answer {
+        Some(Some(answer)) => format!(r#""answer":{},"#, json_string(answer)),
+        Some(None) => r#""answer":null,"#.to_string(),
+        None => String::new(),
+    };
+    format!(
+        r#"{{{answer}"elapsed_ns":{},"memory":{}}}"#,
+        elapsed.as_nanos(),
+        mem_json(mem)
+    )
+}
+
+fn mem_json(mem: Option<MemStats>) -> String {
+    match mem {
+        Some(mem) => format!(
+            r#"{{"allocated":{},"allocations":{},"peak":{}}}"#,
+            mem.allocated, mem.allocations, mem.peak
+        ),
+        None => "null".to_string(),
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut escaped = String::from('"');
+    for c in text.chars() {
+        match c {
+            '"' => escaped += "\\\"",
+            '\\' => escaped += "\\\\",
+            '\n' => escaped += "\\n",
+            c if c.is_control() => escaped += &format!("\\u{:04x}", c as u32),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
 }
@@ -0,0 +1,219 @@
+/*
+ * Heap usage of each phase of a solution, counted by a global allocator.
+ * Only counts with the `alloc-stats` feature, e.g. `cargo solve 19 --features alloc-stats`.
+ */
+use std::fmt::{self, Display};
+use std::ops::Add;
+
+/// Heap usage while running one phase: bytes and number of allocations made, and the most
+/// bytes that were live at once on top of what was already allocated when the phase started.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemStats {
+    pub allocated: u64,
+    pub allocations: u64,
+    pub peak: u64,
+}
+
+/// Adding phases that run one after another sums their allocations and keeps the higher peak.
+impl Add for MemStats {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            allocated: self.allocated + other.allocated,
+            allocations: self.allocations + other.allocations,
+            peak: self.peak.max(other.peak),
+        }
+    }
+}
+
+impl std::iter::Sum for MemStats {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |a, b| a + b)
+    }
+}
+
+/// Prints as `allocated: 1.50 KiB in 12 allocations, peak: 800 B`. The figures are rounded,
+/// see [`PhaseReport`](crate::PhaseReport) for exact ones.
+impl Display for MemStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "allocated: {} in {} allocations, peak: {}",
+            Bytes(self.allocated),
+            self.allocations,
+            Bytes(self.peak)
+        )
+    }
+}
+
+const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+/// A byte count printed with a binary unit, e.g. `1.50 KiB`.
+pub struct Bytes(pub u64);
+
+impl Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut value = self.0 as f64;
+        let mut unit = 0;
+        while value >= 1024_f64 && unit < UNITS.len() - 1 {
+            value /= 1024_f64;
+            unit += 1;
+        }
+        match unit {
+            0 => write!(f, "{} B", self.0),
+            _ => write!(f, "{value:.2} {}", UNITS[unit]),
+        }
+    }
+}
+
+/// Runs `f` and returns its heap usage, or `None` without the `alloc-stats` feature.
+///
+/// Phases are measured one at a time: the peak of a nested call resets the outer one.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<MemStats>) {
+    #[cfg(feature = "alloc-stats")]
+    {
+        let (result, stats) = counting::measure(f);
+        (result, Some(stats))
+    }
+    #[cfg(not(feature = "alloc-stats"))]
+    {
+        (f(), None)
+    }
+}
+
+#[cfg(feature = "alloc-stats")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use super::MemStats;
+
+    static ALLOCATED: AtomicU64 = AtomicU64::new(0);
+    static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+    static LIVE: AtomicU64 = AtomicU64::new(0);
+    static PEAK: AtomicU64 = AtomicU64::new(0);
+
+    /// Forwards to the system allocator and counts every allocation.
+    struct CountingAllocator;
+
+    #[global_allocator]
+    static GLOBAL: CountingAllocator = CountingAllocator;
+
+    fn record_alloc(size: usize) {
+        let size = size as u64;
+        ALLOCATED.fetch_add(size, Ordering::Relaxed);
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(live, Ordering::Relaxed);
+    }
+
+    fn record_dealloc(size: usize) {
+        LIVE.fetch_sub(size as u64, Ordering::Relaxed);
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                record_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                record_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            record_dealloc(layout.size());
+        }
+
+        /// A reallocation counts as a new allocation of `new_size` that frees the old one.
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                record_dealloc(layout.size());
+                record_alloc(new_size);
+            }
+            new_ptr
+        }
+    }
+
+    pub fn measure<T>(f: impl FnOnce() -> T) -> (T, MemStats) {
+        let allocated = ALLOCATED.load(Ordering::Relaxed);
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+        let live = LIVE.load(Ordering::Relaxed);
+        PEAK.store(live, Ordering::Relaxed);
+
+        let result = f();
+
+        let stats = MemStats {
+            allocated: ALLOCATED.load(Ordering::Relaxed) - allocated,
+            allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+            peak: PEAK.load(Ordering::Relaxed).saturating_sub(live),
+        };
+        (result, stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(Bytes(0).to_string(), "0 B");
+        assert_eq!(Bytes(1023).to_string(), "1023 B");
+        assert_eq!(Bytes(1536).to_string(), "1.50 KiB");
+        assert_eq!(Bytes(3 << 30).to_string(), "3.00 GiB");
+        assert_eq!(Bytes(5 << 40).to_string(), "5120.00 GiB");
+    }
+
+    #[test]
+    fn formats_stats() {
+        let stats = MemStats {
+            allocated: 1536,
+            allocations: 12,
+            peak: 800,
+        };
+        assert_eq!(
+            stats.to_string(),
+            "allocated: 1.50 KiB in 12 allocations, peak: 800 B"
+        );
+    }
+
+    #[test]
+    fn sums_allocations_and_keeps_highest_peak() {
+        let stats = |allocated, allocations, peak| MemStats {
+            allocated,
+            allocations,
+            peak,
+        };
+        assert_eq!(
+            [stats(10, 1, 10), stats(30, 2, 20), stats(5, 1, 5)]
+                .into_iter()
+                .sum::<MemStats>(),
+            stats(45, 4, 20)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc-stats")]
+    fn counts_allocations() {
+        let (len, stats) = measure(|| {
+            let a = vec![0_u8; 4096];
+            let b = vec![0_u8; 1024];
+            a.len() + b.len()
+        });
+        let stats = stats.unwrap();
+        assert_eq!(len, 5120);
+        assert!(stats.allocated >= 5120, "{stats}");
+        assert!(stats.allocations >= 2, "{stats}");
+    }
+}
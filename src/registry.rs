@@ -0,0 +1,315 @@
+/*
+ * Keeps the files that list every day in sync, used by `cargo scaffold` and `cargo unscaffold`.
+ * Example import: `use advent_of_code::registry;`.
+ */
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The solution registry, read by `cargo all`.
+pub const DAYS_FILE: &str = "src/days.rs";
+/// One test per day, running the day's tests with its example tests.
+pub const EXAMPLES_INDEX: &str = "tests/examples.rs";
+/// VS Code debug configurations.
+pub const LAUNCH_FILE: &str = ".vscode/launch.json";
+
+/// A change to one of the files that list every day.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Edit {
+    /// What the file is, as in "Updated solution registry".
+    pub kind: &'static str,
+    pub path: PathBuf,
+    pub original: String,
+    pub contents: String,
+}
+
+type Update = fn(&str, &[u8], bool) -> Result<String, String>;
+
+/// The changes to the solution registry, the example-test index and the VS Code debug
+/// configurations under `root` that register `days`, or unregister them if `add` is false.
+/// Files that are already up to date are left out.
+pub fn edits(root: &Path, days: &[u8], add: bool) -> Result<Vec<Edit>, String> {
+    let files: [(&str, &str, Update); 3] = [
+        ("solution registry", DAYS_FILE, update_days),
+        ("example-test index", EXAMPLES_INDEX, update_example_tests),
+        (
+            "VS Code debug configurations",
+            LAUNCH_FILE,
+            update_launch_configs,
+        ),
+    ];
+    let mut edits = Vec::new();
+    for (kind, path, update) in files {
+        let path = root.join(path);
+        let original = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {kind} \"{}\": {e}", path.display()))?;
+        let contents = update(&original, days, add)?;
+        if contents != original {
+            edits.push(Edit {
+                kind,
+                path,
+                original,
+                contents,
+            });
+        }
+    }
+    Ok(edits)
+}
+
+/// Days like `7`, `1-25` or `1,3,5-7`, in order and without duplicates.
+pub fn parse_days(text: &str) -> Result<Vec<u8>, String> {
+    let day = |s: &str| match s.trim().parse::<u8>() {
+        Ok(day @ 1..=25) => Ok(day),
+        _ => Err(format!("`{s}` is not a day between 1 and 25")),
+    };
+    let mut days = Vec::new();
+    for part in text.split(',') {
+        match part.split_once('-') {
+            Some((first, last)) => {
+                let (first, last) = (day(first)?, day(last)?);
+                if first > last {
+                    return Err(format!("`{part}` is an empty range"));
+                }
+                days.extend(first..=last);
+            }
+            None => days.push(day(part)?),
+        }
+    }
+    days.sort_unstable();
+    days.dedup();
+    Ok(days)
+}
+
+/// Adds `days` to the registry or, if `add` is false, removes them from it.
+fn update(registered: &[u8], days: &[u8], add: bool) -> Vec<u8> {
+    let mut registered = registered
+        .iter()
+        .copied()
+        .filter(|day| !days.contains(day))
+        .collect::<Vec<_>>();
+    if add {
+        registered.extend(days);
+    }
+    registered.sort_unstable();
+    registered.dedup();
+    registered
+}
+
+/// Splits `text` around the part between `start` and the following `end`.
+fn split<'a>(
+    text: &'a str,
+    start: &str,
+    end: &str,
+    file: &str,
+) -> Result<(&'a str, &'a str, &'a str), String> {
+    let missing = || format!("could not find `{}` in {file}", start.trim());
+    let from = text.find(start).ok_or_else(missing)? + start.len();
+    let to = from + text[from..].find(end).ok_or_else(missing)?;
+    Ok((&text[..from], &text[from..to], &text[to..]))
+}
+
+fn parse_day(entry: &str, file: &str) -> Result<u8, String> {
+    entry
+        .trim()
+        .trim_matches('"')
+        .parse()
+        .map_err(|_| format!("`{}` in {file} is not a day", entry.trim()))
+}
+
+/// The lines of a list with `per_line` entries each.
+fn rows(entries: Vec<String>, per_line: usize) -> String {
+    entries
+        .chunks(per_line)
+        .map(|row| format!("    {},\n", row.join(", ")))
+        .collect()
+}
+
+/// `src/days.rs` with `days` added or removed.
+pub fn update_days(text: &str, days: &[u8], add: bool) -> Result<String, String> {
+    let (head, list, tail) = split(text, "pub const DAYS: &[u8] = &[\n", "];", DAYS_FILE)?;
+    let registered = list
+        .split(',')
+        .filter(|entry| !entry.trim().is_empty())
+        .map(|entry| parse_day(entry, DAYS_FILE))
+        .collect::<Result<Vec<_>, _>>()?;
+    let entries = update(&registered, days, add)
+        .iter()
+        .map(u8::to_string)
+        .collect();
+    Ok(format!("{head}{}{tail}", rows(entries, 10)))
+}
+
+/// `tests/examples.rs` with the test modules for `days` added or removed.
+pub fn update_example_tests(text: &str, days: &[u8], add: bool) -> Result<String, String> {
+    let (head, list, tail) = split(text, "example_tests!(\n", ");", EXAMPLES_INDEX)?;
+    let registered = list
+        .split(',')
+        .filter(|entry| !entry.trim().is_empty())
+        .map(|entry| match entry.split_once("=>") {
+            Some((_, day)) => parse_day(day, EXAMPLES_INDEX),
+            None => Err(format!(
+                "expected `dayDD => \"DD\"` in {EXAMPLES_INDEX}, found `{}`",
+                entry.trim()
+            )),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let entries = update(&registered, days, add)
+        .iter()
+        .map(|day| format!("day{day:02} => \"{day:02}\""))
+        .collect();
+    Ok(format!("{head}{}{tail}", rows(entries, 5)))
+}
+
+fn launch_config(day: u8) -> String {
+    format!(
+        r#"{{
+            "type": "lldb",
+            "request": "launch",
+            "name": "Debug day {day:02}",
+            "cargo": {{
+                "args": ["build", "--bin={day:02}", "--package=advent_of_code"],
+                "filter": {{
+                    "name": "{day:02}",
+                    "kind": "bin"
+                }}
+            }},
+            "args": [],
+            "cwd": "${{workspaceFolder}}"
+        }}"#
+    )
+}
+
+/// The top level objects of a JSON array body, as they are written.
+fn objects(list: &str) -> Result<Vec<&str>, String> {
+    let mut objects = Vec::new();
+    let (mut depth, mut start, mut in_string, mut escaped) = (0, 0, false, false);
+    for (i, c) in list.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            _ if in_string => {}
+            '{' => {
+                if depth == 0 {
+                    start = i;
+                }
+                depth += 1;
+            }
+            '}' if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    objects.push(&list[start..=i]);
+                }
+            }
+            _ => {}
+        }
+    }
+    match depth {
+        0 => Ok(objects),
+        _ => Err(format!("unbalanced braces in {LAUNCH_FILE}")),
+    }
+}
+
+/// The day of a debug configuration generated by [`launch_config`].
+fn launch_config_day(object: &str) -> Option<u8> {
+    let (_, rest) = object.split_once(r#""name": "Debug day "#)?;
+    rest.get(..2)?.parse().ok()
+}
+
+/// `.vscode/launch.json` with the debug configurations for `days` added or removed. Other
+/// configurations are kept as they are, and the days' configurations follow them in order.
+pub fn update_launch_configs(text: &str, days: &[u8], add: bool) -> Result<String, String> {
+    let (head, list, tail) = split(text, "\"configurations\": [", "\n    ]", LAUNCH_FILE)?;
+    let objects = objects(list)?;
+    let registered = objects
+        .iter()
+        .filter_map(|object| launch_config_day(object))
+        .collect::<Vec<_>>();
+    let generated = update(&registered, days, add)
+        .into_iter()
+        .map(launch_config)
+        .collect::<Vec<_>>();
+    let configs = objects
+        .into_iter()
+        .filter(|object| launch_config_day(object).is_none())
+        .chain(generated.iter().map(String::as_str))
+        .map(|object| format!("\n        {object}"))
+        .collect::<Vec<_>>();
+    Ok(format!("{head}{}{tail}", configs.join(",")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_days() {
+        assert_eq!(parse_days("7"), Ok(vec![7]));
+        assert_eq!(parse_days("1-25"), Ok((1..=25).collect()));
+        assert_eq!(parse_days("5-7,1,6"), Ok(vec![1, 5, 6, 7]));
+        assert!(parse_days("0").is_err());
+        assert!(parse_days("3-26").is_err());
+        assert!(parse_days("7-5").is_err());
+        assert!(parse_days("seven").is_err());
+    }
+
+    #[test]
+    fn updates_days() {
+        let text = "/// Days.\n#[rustfmt::skip]\npub const DAYS: &[u8] = &[\n    1, 2, 3, 4, 5, 6, 7, 8, 9, 10,\n    12,\n];\n";
+        let added = update_days(text, &[11, 3], true).unwrap();
+        assert_eq!(
+            added,
+            "/// Days.\n#[rustfmt::skip]\npub const DAYS: &[u8] = &[\n    1, 2, 3, 4, 5, 6, 7, 8, 9, 10,\n    11, 12,\n];\n"
+        );
+        assert_eq!(update_days(&added, &[11], false).unwrap(), text);
+        assert!(update_days("pub const DAYS: &[u8] = &[\n    x,\n];", &[1], true).is_err());
+        assert!(update_days("", &[1], true).is_err());
+    }
+
+    #[test]
+    fn updates_example_tests() {
+        let text = "example_tests!(\n    day01 => \"01\",\n);\n";
+        let added = update_example_tests(text, &[2], true).unwrap();
+        assert_eq!(
+            added,
+            "example_tests!(\n    day01 => \"01\", day02 => \"02\",\n);\n"
+        );
+        assert_eq!(update_example_tests(&added, &[2], false).unwrap(), text);
+        let empty = update_example_tests(text, &[1], false).unwrap();
+        assert_eq!(empty, "example_tests!(\n);\n");
+        assert_eq!(update_example_tests(&empty, &[1], true).unwrap(), text);
+    }
+
+    #[test]
+    fn updates_launch_configs() {
+        let text = r#"{
+    // comment
+    "version": "0.2.0",
+    "configurations": [
+        {
+            "name": "Debug unit tests",
+            "cwd": "${workspaceFolder}"
+        }
+    ]
+}
+"#;
+        let added = update_launch_configs(text, &[7, 3], true).unwrap();
+        assert!(added.starts_with(
+            "{\n    // comment\n    \"version\": \"0.2.0\",\n    \"configurations\": [\n        {\n            \"name\": \"Debug unit tests\",\n            \"cwd\": \"${workspaceFolder}\"\n        },\n        {\n"
+        ));
+        let days = objects(&added)
+            .unwrap()
+            .iter()
+            .flat_map(|object| objects(&object[1..object.len() - 1]).unwrap())
+            .filter_map(launch_config_day)
+            .collect::<Vec<_>>();
+        assert_eq!(days, [3, 7]);
+        assert!(
+            added.ends_with("            \"cwd\": \"${workspaceFolder}\"\n        }\n    ]\n}\n")
+        );
+
+        let again = update_launch_configs(&added, &[3], true).unwrap();
+        assert_eq!(again, added);
+        let removed = update_launch_configs(&added, &[3, 7], false).unwrap();
+        assert_eq!(removed, text);
+    }
+}
@@ -0,0 +1,246 @@
+/*
+ * Named, nested timing spans inside a solution, reported as a tree after each part.
+ * Example: `let _span = advent_of_code::aoc_span!("floyd_warshall");`.
+ */
+use std::cell::RefCell;
+use std::fmt::Write;
+use std::marker::PhantomData;
+use std::time::{Duration, Instant};
+
+/// Total time spent in a span and how often it was entered, with the spans entered inside it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpanNode {
+    pub name: &'static str,
+    pub elapsed: Duration,
+    pub calls: u64,
+    pub children: Vec<SpanNode>,
+}
+
+#[derive(Debug)]
+struct Node {
+    name: &'static str,
+    elapsed: Duration,
+    calls: u64,
+    children: Vec<usize>,
+}
+
+/// Spans recorded on one thread: a tree stored as a list of nodes, and the open spans.
+#[derive(Debug, Default)]
+struct Spans {
+    nodes: Vec<Node>,
+    roots: Vec<usize>,
+    open: Vec<usize>,
+}
+
+impl Spans {
+    fn enter(&mut self, name: &'static str) -> usize {
+        let siblings = match self.open.last() {
+            Some(&parent) => &self.nodes[parent].children,
+            None => &self.roots,
+        };
+        let node = match siblings.iter().find(|&&i| self.nodes[i].name == name) {
+            Some(&node) => node,
+            None => {
+                let node = self.nodes.len();
+                self.nodes.push(Node {
+                    name,
+                    elapsed: Duration::ZERO,
+                    calls: 0,
+                    children: Vec::new(),
+                });
+                match self.open.last() {
+                    Some(&parent) => self.nodes[parent].children.push(node),
+                    None => self.roots.push(node),
+                }
+                node
+            }
+        };
+        self.open.push(node);
+        node
+    }
+
+    fn exit(&mut self, node: usize, elapsed: Duration) {
+        // spans dropped out of order also close the spans opened after them
+        if let Some(at) = self.open.iter().rposition(|&open| open == node) {
+            self.open.truncate(at);
+        }
+        self.nodes[node].elapsed += elapsed;
+        self.nodes[node].calls += 1;
+    }
+
+    fn tree(&self, node: usize) -> SpanNode {
+        let node = &self.nodes[node];
+        SpanNode {
+            name: node.name,
+            elapsed: node.elapsed,
+            calls: node.calls,
+            children: node.children.iter().map(|&i| self.tree(i)).collect(),
+        }
+    }
+}
+
+thread_local! {
+    static SPANS: RefCell<Spans> = RefCell::new(Spans::default());
+}
+
+/// An open span, closed and recorded when dropped. Spans are recorded per thread.
+#[must_use = "a span is closed as soon as it is dropped, bind it with `let _span = ...`"]
+pub struct Span {
+    node: usize,
+    start: Instant,
+    _thread: PhantomData<*const ()>,
+}
+
+/// Opens a span called `name` inside the innermost open span, see [`aoc_span!`](crate::aoc_span).
+pub fn enter(name: &'static str) -> Span {
+    let node = SPANS.with(|spans| spans.borrow_mut().enter(name));
+    Span {
+        node,
+        start: Instant::now(),
+        _thread: PhantomData,
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        let elapsed = self.start.elapsed();
+        SPANS.with(|spans| spans.borrow_mut().exit(self.node, elapsed));
+    }
+}
+
+/// Returns the spans recorded on this thread so far and starts over. Spans that are still
+/// open are only recorded once they close.
+pub fn take() -> Vec<SpanNode> {
+    SPANS.with(|spans| {
+        let mut spans = spans.borrow_mut();
+        let roots = spans.roots.iter().map(|&i| spans.tree(i)).collect();
+        if spans.open.is_empty() {
+            *spans = Spans::default();
+        }
+        roots
+    })
+}
+
+/// Formats spans as an indented tree, with the share of its parent's time for every span
+/// and the share of `total` for the outermost ones.
+pub fn report(roots: &[SpanNode], total: Duration) -> String {
+    fn width(node: &SpanNode, depth: usize) -> usize {
+        node.children
+            .iter()
+            .map(|child| width(child, depth + 1))
+            .fold(2 * depth + node.name.chars().count(), usize::max)
+    }
+
+    fn write_node(out: &mut String, node: &SpanNode, depth: usize, parent: Duration, width: usize) {
+        let share = match parent.as_secs_f64() {
+            p if p > 0_f64 => 100_f64 * node.elapsed.as_secs_f64() / p,
+            _ => 0_f64,
+        };
+        let calls = match node.calls {
+            1 => "1 call".to_string(),
+            n => format!("{n} calls"),
+        };
+        let name = format!("{}{}", "  ".repeat(depth), node.name);
+        let elapsed = format!("{:.2?}", node.elapsed);
+        writeln!(out, "{name:width$}  {elapsed:>10} {share:>6.1}%  {calls}").unwrap();
+        for child in &node.children {
+            write_node(out, child, depth + 1, node.elapsed, width);
+        }
+    }
+
+    let width = roots.iter().map(|root| width(root, 0)).max().unwrap_or(0);
+    let mut out = String::new();
+    for root in roots {
+        write_node(&mut out, root, 0, total, width);
+    }
+    out
+}
+
+/// Opens a named timing span that closes when the returned guard is dropped.
+/// Spans opened while another one is open are nested inside it, and repeated spans
+/// add up their time and calls. [`solve!`](crate::solve) prints the tree after each part.
+///
+/// ```ignore
+/// let _span = aoc_span!("floyd_warshall");
+/// ```
+#[macro_export]
+macro_rules! aoc_span {
+    ($name:expr) => {
+        $crate::span::enter($name)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shape(nodes: &[SpanNode]) -> Vec<(&'static str, u64, usize)> {
+        nodes
+            .iter()
+            .map(|n| (n.name, n.calls, n.children.len()))
+            .collect()
+    }
+
+    #[test]
+    fn records_nested_spans() {
+        take();
+        {
+            let _outer = crate::aoc_span!("outer");
+            for _ in 0..3 {
+                let _inner = crate::aoc_span!("inner");
+            }
+            let _other = crate::aoc_span!("other");
+        }
+        let _second = crate::aoc_span!("second");
+        drop(_second);
+
+        let roots = take();
+        assert_eq!(shape(&roots), [("outer", 1, 2), ("second", 1, 0)]);
+        assert_eq!(
+            shape(&roots[0].children),
+            [("inner", 3, 0), ("other", 1, 0)]
+        );
+        assert!(roots[0].elapsed >= roots[0].children[0].elapsed);
+        assert!(take().is_empty());
+    }
+
+    #[test]
+    fn same_name_in_different_parents() {
+        take();
+        for parent in ["a", "b"] {
+            let _parent = enter(parent);
+            let _child = enter("child");
+        }
+        let roots = take();
+        assert_eq!(shape(&roots), [("a", 1, 1), ("b", 1, 1)]);
+        assert_eq!(shape(&roots[1].children), [("child", 1, 0)]);
+    }
+
+    #[test]
+    fn out_of_order_drop_closes_inner_spans() {
+        take();
+        let outer = enter("outer");
+        let inner = enter("inner");
+        drop(outer);
+        let _after = enter("after");
+        drop(_after);
+        drop(inner);
+        let roots = take();
+        assert_eq!(shape(&roots), [("outer", 1, 1), ("after", 1, 0)]);
+    }
+
+    #[test]
+    fn formats_tree() {
+        let node = |name, millis, calls, children| SpanNode {
+            name,
+            elapsed: Duration::from_millis(millis),
+            calls,
+            children,
+        };
+        let roots = [node("distances", 8, 1, vec![node("bfs", 2, 15, vec![])])];
+        assert_eq!(
+            report(&roots, Duration::from_millis(10)),
+            "distances      8.00ms   80.0%  1 call\n  bfs          2.00ms   25.0%  15 calls\n"
+        );
+    }
+}
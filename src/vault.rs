@@ -0,0 +1,200 @@
+/*
+ * Encrypted copies of the puzzle inputs, so they can be committed without sharing them.
+ * Example import: `use advent_of_code::vault::{self, Key};`.
+ *
+ * Inputs are encrypted with AES-256-GCM-SIV, with the input's name as associated data. The
+ * nonce is fixed: GCM-SIV stays safe when a nonce is reused, and encrypting the same input
+ * twice gives the same file, so re-running `cargo vault encrypt` leaves git clean.
+ */
+use std::env;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::fs;
+use std::path::Path;
+
+use aes_gcm_siv::aead::{Aead, KeyInit, Payload};
+use aes_gcm_siv::{Aes256GcmSiv, Nonce};
+
+use crate::config::Config;
+
+/// Holds the vault key, and takes precedence over the key file.
+pub const KEY_VAR: &str = "AOC_VAULT_KEY";
+
+/// Starts every encrypted file, in case the format ever has to change.
+const MAGIC: &[u8] = b"aoc-vault 2\n";
+/// Started the files of the first format, ChaCha20 with a hand-written HMAC-SHA256.
+const OLD_MAGIC: &[u8] = b"aoc-vault 1\n";
+const NONCE: [u8; 12] = [0; 12];
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum VaultError {
+    /// Neither `AOC_VAULT_KEY` nor the key file is set.
+    NoKey(String),
+    /// The key is not 32 random bytes written as hex.
+    WeakKey,
+    Io(String),
+    /// The file is not an encrypted input, or was made with another key or for another input.
+    Invalid(String),
+}
+
+impl Display for VaultError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VaultError::NoKey(path) => write!(
+                f,
+                "no vault key, set {KEY_VAR} or write the key to \"{path}\""
+            ),
+            VaultError::WeakKey => write!(
+                f,
+                "the vault key must be 64 hex characters, create one with `openssl rand -hex 32`"
+            ),
+            VaultError::Io(e) => write!(f, "{e}"),
+            VaultError::Invalid(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl Error for VaultError {}
+
+/// The vault key, ready to encrypt and decrypt.
+#[derive(Clone)]
+pub struct Key {
+    cipher: Aes256GcmSiv,
+}
+
+impl fmt::Debug for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Key(..)")
+    }
+}
+
+impl Key {
+    /// Reads the key from 32 random bytes written as 64 hex characters, the output of
+    /// `openssl rand -hex 32`. The encrypted inputs may be public, so anything weaker, such as
+    /// a passphrase that could be guessed offline, is rejected. Surrounding whitespace is
+    /// ignored, so a key file may end with a newline.
+    pub fn new(hex: &str) -> Result<Self, VaultError> {
+        let hex = hex.trim();
+        if hex.len() != 64 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(VaultError::WeakKey);
+        }
+        let mut master = [0; 32];
+        for (i, byte) in master.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).unwrap();
+        }
+        Ok(Self {
+            cipher: Aes256GcmSiv::new(&master.into()),
+        })
+    }
+
+    /// The key from `AOC_VAULT_KEY`, else from the key file in `aoc.toml` under `root`.
+    pub fn load(root: &Path, config: &Config) -> Result<Self, VaultError> {
+        let path = root.join(config.key_path());
+        let secret = match env::var(KEY_VAR) {
+            Ok(secret) => secret,
+            Err(_) => fs::read_to_string(&path).unwrap_or_default(),
+        };
+        if secret.trim().is_empty() {
+            return Err(VaultError::NoKey(path.display().to_string()));
+        }
+        Self::new(&secret)
+    }
+}
+
+/// Encrypts the input called `name`, e.g. `"07"`. The name is authenticated too, so a file
+/// renamed to another day does not decrypt.
+pub fn encrypt(key: &Key, name: &str, text: &str) -> Vec<u8> {
+    let payload = Payload {
+        msg: text.as_bytes(),
+        aad: name.as_bytes(),
+    };
+    let sealed = key
+        .cipher
+        .encrypt(Nonce::from_slice(&NONCE), payload)
+        .expect("inputs are far below the size limit of AES-GCM-SIV");
+    [MAGIC, &sealed].concat()
+}
+
+/// Decrypts what [`encrypt`] made of the input called `name`.
+pub fn decrypt(key: &Key, name: &str, data: &[u8]) -> Result<String, VaultError> {
+    if data.starts_with(OLD_MAGIC) {
+        return Err(VaultError::Invalid(format!(
+            "`{name}` is in the old vault format, run `cargo vault encrypt` with its input in place"
+        )));
+    }
+    let sealed = data
+        .strip_prefix(MAGIC)
+        .ok_or_else(|| VaultError::Invalid(format!("`{name}` is not an encrypted input")))?;
+    let payload = Payload {
+        msg: sealed,
+        aad: name.as_bytes(),
+    };
+    let text = key
+        .cipher
+        .decrypt(Nonce::from_slice(&NONCE), payload)
+        .map_err(|_| {
+            VaultError::Invalid(format!(
+                "could not decrypt `{name}`: wrong key, or the file was changed or renamed"
+            ))
+        })?;
+    String::from_utf8(text)
+        .map_err(|_| VaultError::Invalid(format!("`{name}` decrypted to invalid UTF-8")))
+}
+
+/// Decrypts the vault's copy of the input called `name`, for `read_file` when the input
+/// itself is missing.
+pub fn read(root: &Path, config: &Config, name: &str) -> Result<String, VaultError> {
+    let path = root.join(config.vault_path(name));
+    let data = fs::read(&path)
+        .map_err(|e| VaultError::Io(format!("could not read \"{}\": {e}", path.display())))?;
+    decrypt(&Key::load(root, config)?, name, &data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+    const OTHER_KEY: &str = "fedcba9876543210fedcba9876543210fedcba9876543210fedcba9876543210";
+
+    #[test]
+    fn round_trips() {
+        let key = Key::new(&format!("{KEY}\n")).unwrap();
+        let input = "1000\n2000\n\n3000\n".repeat(20);
+        let data = encrypt(&key, "01", &input);
+        assert!(!data.windows(4).any(|w| w == b"1000"));
+        assert_eq!(data, encrypt(&key, "01", &input));
+        assert_eq!(decrypt(&key, "01", &data), Ok(input.clone()));
+        assert_eq!(
+            decrypt(&Key::new(&KEY.to_uppercase()).unwrap(), "01", &data),
+            Ok(input)
+        );
+
+        let invalid = |result| matches!(result, Err(VaultError::Invalid(_)));
+        assert!(invalid(decrypt(&Key::new(OTHER_KEY).unwrap(), "01", &data)));
+        assert!(invalid(decrypt(&key, "02", &data)));
+        let mut changed = data.clone();
+        *changed.last_mut().unwrap() ^= 1;
+        assert!(invalid(decrypt(&key, "01", &changed)));
+        assert!(invalid(decrypt(&key, "01", b"1000\n2000\n")));
+        assert!(invalid(decrypt(&key, "01", MAGIC)));
+        let old = [OLD_MAGIC, &data[MAGIC.len()..]].concat();
+        assert!(matches!(
+            decrypt(&key, "01", &old),
+            Err(VaultError::Invalid(e)) if e.contains("old vault format")
+        ));
+    }
+
+    #[test]
+    fn rejects_weak_keys() {
+        for key in [
+            "",
+            "correct horse battery staple",
+            &KEY[1..],
+            &format!("{KEY}0"),
+            &KEY.replace('a', "g"),
+        ] {
+            assert_eq!(Key::new(key).unwrap_err(), VaultError::WeakKey, "{key}");
+        }
+    }
+}
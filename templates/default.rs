@@ -0,0 +1,29 @@
+use advent_of_code::{Params, Solution};
+
+/// {{title}}, https://adventofcode.com/{{year}}/day/{{day}}
+struct Day{{day_padded}};
+
+impl Solution for Day{{day_padded}} {
+    type Parsed<'a> = &'a str;
+    type PartOne = {{answer_type}};
+    type PartTwo = {{answer_type}};
+
+    fn parse(input: &str) -> advent_of_code::Result<Self::Parsed<'_>> {
+        Ok(input)
+    }
+
+    fn part_one(input: &Self::Parsed<'_>, _: &Params) -> Option<Self::PartOne> {
+        None
+    }
+
+    fn part_two(input: &Self::Parsed<'_>, _: &Params) -> Option<Self::PartTwo> {
+        None
+    }
+}
+
+fn main() {
+    let input = &advent_of_code::read_file("inputs", {{day}});
+    advent_of_code::solve!(Day{{day_padded}}, input);
+}
+
+advent_of_code::aoc_test!(Day{{day_padded}}, day = {{day}}, part_one = None, part_two = None);
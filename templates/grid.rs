@@ -0,0 +1,63 @@
+use advent_of_code::{Params, ParseError, Solution};
+
+/// {{title}}, https://adventofcode.com/{{year}}/day/{{day}}
+struct Day{{day_padded}};
+
+/// A rectangle of characters, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Grid {
+    cells: Vec<u8>,
+    width: usize,
+    height: usize,
+}
+
+impl Grid {
+    fn get(&self, x: usize, y: usize) -> Option<u8> {
+        (x < self.width && y < self.height).then(|| self.cells[y * self.width + x])
+    }
+
+    /// The positions next to `(x, y)` inside the grid, without diagonals.
+    fn neighbors(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        [(0, -1), (1, 0), (0, 1), (-1, 0)]
+            .into_iter()
+            .filter_map(move |(dx, dy)| {
+                let x = x.checked_add_signed(dx)?;
+                let y = y.checked_add_signed(dy)?;
+                self.get(x, y).map(|_| (x, y))
+            })
+    }
+}
+
+impl Solution for Day{{day_padded}} {
+    type Parsed<'a> = Grid;
+    type PartOne = {{answer_type}};
+    type PartTwo = {{answer_type}};
+
+    fn parse(input: &str) -> advent_of_code::Result<Self::Parsed<'_>> {
+        let rows = input.lines().map(str::as_bytes).collect::<Vec<_>>();
+        let width = rows.first().map_or(0, |row| row.len());
+        if let Some(y) = rows.iter().position(|row| row.len() != width) {
+            return Err(ParseError::new(format!("Row {y} is not {width} wide")));
+        }
+        Ok(Grid {
+            cells: rows.concat(),
+            width,
+            height: rows.len(),
+        })
+    }
+
+    fn part_one(grid: &Self::Parsed<'_>, _: &Params) -> Option<Self::PartOne> {
+        None
+    }
+
+    fn part_two(grid: &Self::Parsed<'_>, _: &Params) -> Option<Self::PartTwo> {
+        None
+    }
+}
+
+fn main() {
+    let input = &advent_of_code::read_file("inputs", {{day}});
+    advent_of_code::solve!(Day{{day_padded}}, input);
+}
+
+advent_of_code::aoc_test!(Day{{day_padded}}, day = {{day}}, part_one = None, part_two = None);
@@ -0,0 +1,44 @@
+use nom::{
+    bytes::complete::is_not,
+    character::complete::newline,
+    multi::separated_list0,
+    IResult,
+};
+
+use advent_of_code::{Params, ParseError, Solution};
+
+/// {{title}}, https://adventofcode.com/{{year}}/day/{{day}}
+struct Day{{day_padded}};
+
+impl Solution for Day{{day_padded}} {
+    type Parsed<'a> = Vec<&'a str>;
+    type PartOne = {{answer_type}};
+    type PartTwo = {{answer_type}};
+
+    fn parse(input: &str) -> advent_of_code::Result<Self::Parsed<'_>> {
+        let (rest, lines) = separated_list0(newline, line)(input)?;
+        if !rest.trim().is_empty() {
+            return Err(ParseError::new(format!("Unexpected input: {rest}")));
+        }
+        Ok(lines)
+    }
+
+    fn part_one(lines: &Self::Parsed<'_>, _: &Params) -> Option<Self::PartOne> {
+        None
+    }
+
+    fn part_two(lines: &Self::Parsed<'_>, _: &Params) -> Option<Self::PartTwo> {
+        None
+    }
+}
+
+fn line(input: &str) -> IResult<&str, &str> {
+    is_not("\r\n")(input)
+}
+
+fn main() {
+    let input = &advent_of_code::read_file("inputs", {{day}});
+    advent_of_code::solve!(Day{{day_padded}}, input);
+}
+
+advent_of_code::aoc_test!(Day{{day_padded}}, day = {{day}}, part_one = None, part_two = None);
@@ -0,0 +1,82 @@
+/*
+ * The example-test index: one test per day in the solution registry, running the day's own
+ * tests with its `aoc_test!` example tests, and a check that the registry matches `src/bin`.
+ * The list is kept up to date by `cargo scaffold` and `cargo unscaffold`.
+ * Run a single day with `cargo test --test examples day07`.
+ */
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use advent_of_code::config::Config;
+use advent_of_code::days::DAYS;
+
+fn config() -> Config {
+    Config::load(Path::new(env!("CARGO_MANIFEST_DIR"))).unwrap_or_else(|e| panic!("{e}"))
+}
+
+fn check(day: &str, bin: Option<&str>) {
+    let day: u8 = day.parse().unwrap();
+    assert!(
+        DAYS.contains(&day),
+        "day {day:02} is not in the solution registry"
+    );
+    assert!(bin.is_some(), "day {day:02} has no binary");
+    let manifest = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let example = manifest.join(config().example_path(day));
+    assert!(example.exists(), "{} not found", example.display());
+
+    let output = Command::new(env::var("CARGO").unwrap_or("cargo".into()))
+        .args([
+            "test",
+            "--offline",
+            "--quiet",
+            "--bin",
+            &format!("{day:02}"),
+        ])
+        // the running `cargo test` holds the lock on the usual target directory
+        .env("CARGO_TARGET_DIR", manifest.join("target/examples-check"))
+        .current_dir(manifest)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "the tests of day {day:02} failed\n{}\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn registry_matches_solutions() {
+    let solutions = Path::new(env!("CARGO_MANIFEST_DIR")).join(config().solutions);
+    let mut days = fs::read_dir(solutions)
+        .unwrap()
+        .filter_map(|entry| {
+            let name = entry.unwrap().file_name().into_string().ok()?;
+            name.strip_suffix(".rs")?.parse::<u8>().ok()
+        })
+        .collect::<Vec<_>>();
+    days.sort_unstable();
+    assert_eq!(
+        days, DAYS,
+        "run `cargo scaffold <day> --repair` to register a day"
+    );
+}
+
+macro_rules! example_tests {
+    ($($name:ident => $day:literal),* $(,)?) => {$(
+        #[test]
+        fn $name() {
+            check($day, option_env!(concat!("CARGO_BIN_EXE_", $day)));
+        }
+    )*};
+}
+
+example_tests!(
+    day01 => "01", day02 => "02", day03 => "03", day04 => "04", day05 => "05",
+    day06 => "06", day07 => "07", day08 => "08", day09 => "09", day10 => "10",
+    day11 => "11", day12 => "12", day13 => "13", day14 => "14", day15 => "15",
+    day16 => "16", day18 => "18", day19 => "19", day24 => "24",
+);
@@ -0,0 +1,100 @@
+/*
+ * Checks every day against the real input in `src/inputs/DD.txt` and the answers recorded in
+ * `src/answers/DD.txt` (part one on the first line, part two on the second), or wherever
+ * `aoc.toml` puts them.
+ * Inputs missing from a fresh clone are decrypted from `cargo vault`'s copies.
+ * Days without an input, a solution or a recorded answer are skipped.
+ * Run with `cargo test --release --features real-inputs`.
+ */
+#![cfg(feature = "real-inputs")]
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::sync::OnceLock;
+
+use advent_of_code::config::Config;
+
+/// Output of each day's binary, shared by the tests for both parts.
+static OUTPUTS: [OnceLock<String>; 25] = [const { OnceLock::new() }; 25];
+
+fn root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+fn config() -> &'static Config {
+    static CONFIG: OnceLock<Config> = OnceLock::new();
+    CONFIG.get_or_init(|| Config::load(root()).unwrap_or_else(|e| panic!("{e}")))
+}
+
+fn read_non_empty(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .filter(|s| !s.trim().is_empty())
+}
+
+fn run(day: u8, bin: &str) -> &'static str {
+    OUTPUTS[day as usize - 1].get_or_init(|| {
+        let output = Command::new(bin)
+            .current_dir(root())
+            .output()
+            .expect("could not run solution");
+        assert!(
+            output.status.success(),
+            "day {day:02} failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8(output.stdout).unwrap()
+    })
+}
+
+fn check(day: &str, part: usize, bin: Option<&str>) {
+    let day: u8 = day.parse().unwrap();
+    let input = root().join(config().input_path(day));
+    let encrypted = root().join(config().vault_path(&format!("{day:02}")));
+    if read_non_empty(&input).is_none() && !encrypted.is_file() {
+        eprintln!("skipped: {} not found", input.display());
+        return;
+    }
+    let Some(bin) = bin else {
+        eprintln!("skipped: day {day:02} has no solution");
+        return;
+    };
+    let answers = root().join(config().answer_path(day));
+    let expected = read_non_empty(&answers)
+        .and_then(|answers| answers.lines().nth(part - 1).map(|l| l.trim().to_string()))
+        .filter(|answer| !answer.is_empty());
+    let Some(expected) = expected else {
+        eprintln!("skipped: no answer recorded for day {day:02} part {part}");
+        return;
+    };
+
+    let actual = advent_of_code::parse_answers(run(day, bin))[part - 1].clone();
+    assert_eq!(actual, Some(expected), "day {day:02} part {part}");
+}
+
+macro_rules! real_input_tests {
+    ($($name:ident => $day:literal),* $(,)?) => {$(
+        mod $name {
+            const BIN: Option<&str> = option_env!(concat!("CARGO_BIN_EXE_", $day));
+
+            #[test]
+            fn part_one() {
+                super::check($day, 1, BIN);
+            }
+
+            #[test]
+            fn part_two() {
+                super::check($day, 2, BIN);
+            }
+        }
+    )*};
+}
+
+real_input_tests!(
+    day01 => "01", day02 => "02", day03 => "03", day04 => "04", day05 => "05",
+    day06 => "06", day07 => "07", day08 => "08", day09 => "09", day10 => "10",
+    day11 => "11", day12 => "12", day13 => "13", day14 => "14", day15 => "15",
+    day16 => "16", day17 => "17", day18 => "18", day19 => "19", day20 => "20",
+    day21 => "21", day22 => "22", day23 => "23", day24 => "24", day25 => "25",
+);